@@ -7,7 +7,7 @@ use solana_program::{
     program_pack::Pack,
     pubkey::Pubkey,
     rent::Rent,
-    system_instruction::create_account,
+    system_instruction,
     sysvar::Sysvar,
 };
 
@@ -26,6 +26,9 @@ pub fn create_name_account<'a>(
     lamports: u64,
     space: u32,
     signer_seeds: &Vec<u8>,
+    parent_name_opt: Option<&AccountInfo<'a>>,
+    parent_name_owner_opt: Option<&AccountInfo<'a>>,
+    parent_owner_signer_seeds: &[&[u8]],
 ) -> ProgramResult {
     let create_name_instruction = spl_name_service::instruction::create(
         *name_service_program.key,
@@ -38,21 +41,27 @@ pub fn create_name_account<'a>(
         *fee_payer.key,
         *new_owner_account.key,
         None,
-        None,
-        None,
+        parent_name_opt.map(|a| *a.key),
+        parent_name_owner_opt.map(|a| *a.key),
     )?;
 
-    invoke_signed(
-        &create_name_instruction,
-        &[
-            name_service_program.clone(),
-            fee_payer.clone(),
-            name_account.clone(),
-            new_owner_account.clone(),
-            system_program_account.clone(),
-        ],
-        &[&signer_seeds.chunks(32).collect::<Vec<&[u8]>>()],
-    )
+    let fee_payer_seeds = signer_seeds.chunks(32).collect::<Vec<&[u8]>>();
+    let mut accounts = vec![
+        name_service_program.clone(),
+        fee_payer.clone(),
+        name_account.clone(),
+        new_owner_account.clone(),
+        system_program_account.clone(),
+    ];
+    let mut seeds = vec![fee_payer_seeds.as_slice()];
+
+    if let Some(parent_name) = parent_name_opt {
+        accounts.push(parent_name.clone());
+        accounts.push(parent_name_owner_opt.unwrap().clone());
+        seeds.push(parent_owner_signer_seeds);
+    }
+
+    invoke_signed(&create_name_instruction, &accounts, &seeds)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -129,3 +138,128 @@ pub fn create_name_account<'a>(
         invoke_signed(&write_name_instruction, &accounts_update, &[signer_seeds])?;
         Ok(())
     }
+
+pub fn transfer_name_account<'a>(
+    name_service_program: &AccountInfo<'a>,
+    name_account: &AccountInfo<'a>,
+    name_owner: &AccountInfo<'a>,
+    new_owner: &Pubkey,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let transfer_instruction = spl_name_service::instruction::transfer(
+        *name_service_program.key,
+        *new_owner,
+        *name_account.key,
+        *name_owner.key,
+        None,
+    )?;
+
+    invoke_signed(
+        &transfer_instruction,
+        &[
+            name_service_program.clone(),
+            name_account.clone(),
+            name_owner.clone(),
+        ],
+        signers_seeds,
+    )
+}
+
+/// Funds, allocates and assigns a PDA to this program. The account may already hold lamports.
+pub fn create_program_account<'a>(
+    system_program_account: &AccountInfo<'a>,
+    fee_payer: &AccountInfo<'a>,
+    account: &AccountInfo<'a>,
+    space: usize,
+    signer_seeds: &[u8],
+) -> ProgramResult {
+    create_pda_account(
+        system_program_account,
+        fee_payer,
+        account,
+        space,
+        &crate::ID,
+        signer_seeds,
+    )
+}
+
+/// Funds, allocates and assigns a PDA to `owner`. The account may already hold lamports.
+pub fn create_pda_account<'a>(
+    system_program_account: &AccountInfo<'a>,
+    fee_payer: &AccountInfo<'a>,
+    account: &AccountInfo<'a>,
+    space: usize,
+    owner: &Pubkey,
+    signer_seeds: &[u8],
+) -> ProgramResult {
+    let lamports = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account.lamports());
+    let seeds = signer_seeds.chunks(32).collect::<Vec<&[u8]>>();
+
+    if lamports > 0 {
+        invoke(
+            &system_instruction::transfer(fee_payer.key, account.key, lamports),
+            &[
+                fee_payer.clone(),
+                account.clone(),
+                system_program_account.clone(),
+            ],
+        )?;
+    }
+
+    invoke_signed(
+        &system_instruction::allocate(account.key, space as u64),
+        &[account.clone(), system_program_account.clone()],
+        &[&seeds],
+    )?;
+
+    invoke_signed(
+        &system_instruction::assign(account.key, owner),
+        &[account.clone(), system_program_account.clone()],
+        &[&seeds],
+    )
+}
+
+/// Transfers lamports out of a system-owned PDA
+pub fn transfer_signed<'a>(
+    system_program_account: &AccountInfo<'a>,
+    from: &AccountInfo<'a>,
+    to: &AccountInfo<'a>,
+    lamports: u64,
+    signer_seeds: &[u8],
+) -> ProgramResult {
+    invoke_signed(
+        &system_instruction::transfer(from.key, to.key, lamports),
+        &[from.clone(), to.clone(), system_program_account.clone()],
+        &[&signer_seeds.chunks(32).collect::<Vec<&[u8]>>()],
+    )
+}
+
+/// Transfers tokens out of a token account owned by a PDA
+pub fn transfer_tokens_signed<'a>(
+    token_program: &AccountInfo<'a>,
+    from: &AccountInfo<'a>,
+    to: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    amount: u64,
+    signer_seeds: &[u8],
+) -> ProgramResult {
+    invoke_signed(
+        &spl_token::instruction::transfer(
+            token_program.key,
+            from.key,
+            to.key,
+            authority.key,
+            &[],
+            amount,
+        )?,
+        &[
+            from.clone(),
+            to.clone(),
+            authority.clone(),
+            token_program.clone(),
+        ],
+        &[&signer_seeds.chunks(32).collect::<Vec<&[u8]>>()],
+    )
+}
@@ -49,6 +49,34 @@ impl PrintProgramError for AuctionError {
             AuctionError::InvalidVerifier => msg!("Error: Invalid verifier"),
             AuctionError::WrongParent => msg!("Error: Wrong parent owner"),
             AuctionError::WrongClass => msg!("Error: Wrong class"),
+            AuctionError::InvalidDuration => msg!("Error: Invalid auction duration"),
+            AuctionError::BidTooLow => msg!("Error: Bid too low"),
+            AuctionError::AuctionNotStarted => msg!("Error: Auction has not started"),
+            AuctionError::AuctionEnded => msg!("Error: Auction has ended"),
+            AuctionError::AuctionInProgress => msg!("Error: Auction has not ended"),
+            AuctionError::ReserveTooLow => msg!("Error: Reserve price too low"),
+            AuctionError::AuctionHasBids => msg!("Error: Auction has live bids"),
+            AuctionError::InvalidDeadline => msg!("Error: Invalid campaign deadline"),
+            AuctionError::CampaignEnded => msg!("Error: Campaign has ended"),
+            AuctionError::CampaignActive => msg!("Error: Campaign is still active"),
+            AuctionError::CampaignFunded => msg!("Error: Campaign has been funded"),
+            AuctionError::InvalidGoal => msg!("Error: Invalid funding goal"),
+            AuctionError::InvalidConfig => msg!("Error: Invalid config"),
+            AuctionError::NameTooLong => msg!("Error: Name too long"),
+            AuctionError::WrongAuctionKind => msg!("Error: Wrong auction kind"),
+            AuctionError::InvalidStartPrice => msg!("Error: Invalid start price"),
+            AuctionError::InvalidCommitment => msg!("Error: Invalid commitment"),
+            AuctionError::RevealEnded => msg!("Error: Reveal window has ended"),
+            AuctionError::BidLocked => msg!("Error: Bid is still locked"),
+            AuctionError::InvalidBuyNowPrice => msg!("Error: Invalid buy now price"),
+            AuctionError::BuyNowUnavailable => msg!("Error: Buy now is unavailable"),
+            AuctionError::RootNotCreated => msg!("Error: Root domain has not been created"),
+            AuctionError::UnsupportedMint => msg!("Error: Unsupported token mint"),
+            AuctionError::StalePrice => msg!("Error: Oracle price is stale"),
+            AuctionError::UncertainPrice => msg!("Error: Oracle price is too uncertain"),
+            AuctionError::InvalidPrice => msg!("Error: Invalid oracle price"),
+            AuctionError::InvalidReferrer => msg!("Error: Invalid referrer"),
+            AuctionError::InvalidDiscount => msg!("Error: Invalid discount NFT"),
         }
     }
 }
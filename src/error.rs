@@ -32,6 +32,62 @@ pub enum AuctionError {
     WrongParent,
     #[error("Wrong class")]
     WrongClass,
+    #[error("Invalid auction duration")]
+    InvalidDuration,
+    #[error("Bid too low")]
+    BidTooLow,
+    #[error("Auction has not started")]
+    AuctionNotStarted,
+    #[error("Auction has ended")]
+    AuctionEnded,
+    #[error("Auction has not ended")]
+    AuctionInProgress,
+    #[error("Reserve price too low")]
+    ReserveTooLow,
+    #[error("Auction has live bids")]
+    AuctionHasBids,
+    #[error("Invalid campaign deadline")]
+    InvalidDeadline,
+    #[error("Campaign has ended")]
+    CampaignEnded,
+    #[error("Campaign is still active")]
+    CampaignActive,
+    #[error("Campaign has been funded")]
+    CampaignFunded,
+    #[error("Invalid funding goal")]
+    InvalidGoal,
+    #[error("Invalid config")]
+    InvalidConfig,
+    #[error("Name too long")]
+    NameTooLong,
+    #[error("Wrong auction kind")]
+    WrongAuctionKind,
+    #[error("Invalid start price")]
+    InvalidStartPrice,
+    #[error("Invalid commitment")]
+    InvalidCommitment,
+    #[error("Reveal window has ended")]
+    RevealEnded,
+    #[error("Bid is still locked")]
+    BidLocked,
+    #[error("Invalid buy now price")]
+    InvalidBuyNowPrice,
+    #[error("Buy now is unavailable")]
+    BuyNowUnavailable,
+    #[error("Root domain has not been created")]
+    RootNotCreated,
+    #[error("Unsupported token mint")]
+    UnsupportedMint,
+    #[error("Oracle price is stale")]
+    StalePrice,
+    #[error("Oracle price is too uncertain")]
+    UncertainPrice,
+    #[error("Invalid oracle price")]
+    InvalidPrice,
+    #[error("Invalid referrer")]
+    InvalidReferrer,
+    #[error("Invalid discount NFT")]
+    InvalidDiscount,
 }
 
 impl From<AuctionError> for ProgramError {
@@ -0,0 +1,91 @@
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::error::AuctionError;
+
+/// Computes the fee of `fee_bps` basis points on `amount`, discounted when the `discount`
+/// metadata and token accounts prove that `holder` owns an NFT of the discount collection
+///
+/// Only the protocol fees kept by the vault go through here, the donation fee and the auction
+/// settlement fee. The root creation fee funds the rent of the root accounts and is charged in
+/// full.
+pub fn get_fee(
+    amount: u64,
+    fee_bps: u64,
+    holder: &Pubkey,
+    discount: Option<(&AccountInfo, &AccountInfo)>,
+) -> Result<u64, ProgramError> {
+    let fee = amount
+        .checked_mul(fee_bps)
+        .ok_or(AuctionError::NumericalOverflow)?
+        / 10_000;
+    match discount {
+        Some((metadata, token)) => apply_special_discount(fee, holder, metadata, token),
+        None => Ok(fee),
+    }
+}
+
+/// Splits a protocol fee into the share of the referrer and the share of the vault
+pub fn split_referrer_fee(fee: u64, referrer_fee_bps: u64) -> Result<(u64, u64), ProgramError> {
+    let referrer_fee = fee
+        .checked_mul(referrer_fee_bps)
+        .ok_or(AuctionError::NumericalOverflow)?
+        / 10_000;
+    Ok((referrer_fee, fee - referrer_fee))
+}
+
+/// Whether `account` is a token metadata account, which starts the optional discount accounts
+pub fn is_discount_metadata(account: &AccountInfo) -> bool {
+    *account.owner == mpl_token_metadata::ID
+}
+
+#[cfg(not(feature = "no-special-discount-fee"))]
+fn apply_special_discount(
+    fee: u64,
+    holder: &Pubkey,
+    metadata: &AccountInfo,
+    token: &AccountInfo,
+) -> Result<u64, ProgramError> {
+    use bonfida_utils::checks::{check_account_key, check_account_owner};
+    use mpl_token_metadata::accounts::Metadata;
+    use solana_program::{msg, program_pack::Pack};
+
+    use crate::state::constants::{DISCOUNT_COLLECTION, SPECIAL_DISCOUNT_BPS};
+
+    check_account_owner(token, &spl_token::ID)?;
+    let token_account = spl_token::state::Account::unpack(&token.data.borrow())?;
+    if token_account.owner != *holder || token_account.amount == 0 {
+        msg!("The NFT is not held by {}", holder);
+        return Err(AuctionError::InvalidDiscount.into());
+    }
+
+    check_account_owner(metadata, &mpl_token_metadata::ID)?;
+    check_account_key(metadata, &Metadata::find_pda(&token_account.mint).0)?;
+    let metadata = Metadata::from_bytes(&metadata.data.borrow()).map_err(|_| {
+        msg!("Failed to deserialize the NFT metadata");
+        ProgramError::InvalidAccountData
+    })?;
+    match metadata.collection {
+        Some(collection) if collection.verified && collection.key == DISCOUNT_COLLECTION => {}
+        _ => {
+            msg!("The NFT is not a verified member of the discount collection");
+            return Err(AuctionError::InvalidDiscount.into());
+        }
+    }
+
+    let discount = fee
+        .checked_mul(SPECIAL_DISCOUNT_BPS)
+        .ok_or(AuctionError::NumericalOverflow)?
+        / 10_000;
+    msg!("Special discount of {} on the fee", discount);
+    Ok(fee - discount)
+}
+
+#[cfg(feature = "no-special-discount-fee")]
+fn apply_special_discount(
+    fee: u64,
+    _holder: &Pubkey,
+    _metadata: &AccountInfo,
+    _token: &AccountInfo,
+) -> Result<u64, ProgramError> {
+    Ok(fee)
+}
@@ -2,6 +2,11 @@
 //     allocate_and_post_record, allocate_record, delete_record, edit_record, unverify_roa,
 //     validate_ethereum_signature, validate_solana_signature, write_roa,
 // };
+pub use crate::processor::{
+    buy_now, claim_root_rewards, commit_bid, create_auction, delete_auction, init_config,
+    place_bid, reclaim_bid, refund_donation, reveal_bid, settle_auction, unwrap_name,
+    update_config, wrap_name,
+};
 use {
     bonfida_utils::InstructionsAccount,
     borsh::{BorshDeserialize, BorshSerialize},
@@ -18,8 +23,142 @@ pub enum ProgramInstruction {
     CreateAuction,
 
     DeleteAuction,
+
+    PlaceBid,
+
+    SettleAuction,
+
+    RefundDonation,
+
+    InitConfig,
+
+    UpdateConfig,
+
+    BuyNow,
+
+    CommitBid,
+
+    RevealBid,
+
+    ReclaimBid,
+
+    ClaimRootRewards,
+
+    /// Returns a tokenized name to the holder of its NFT. The NFT is not burned: it is escrowed
+    /// in the associated token account of the central state and released by `WrapName` when
+    /// the name is tokenized again, since its master edition cannot be minted twice. Clients
+    /// should treat an NFT held by the central state as unwrapped.
+    UnwrapName,
+
+    WrapName,
+}
+
+#[allow(missing_docs)]
+pub fn create_auction(
+    accounts: create_auction::Accounts<Pubkey>,
+    params: create_auction::Params,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::CreateAuction as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn delete_auction(
+    accounts: delete_auction::Accounts<Pubkey>,
+    params: delete_auction::Params,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::DeleteAuction as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn place_bid(accounts: place_bid::Accounts<Pubkey>, params: place_bid::Params) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::PlaceBid as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn settle_auction(
+    accounts: settle_auction::Accounts<Pubkey>,
+    params: settle_auction::Params,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::SettleAuction as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn refund_donation(
+    accounts: refund_donation::Accounts<Pubkey>,
+    params: refund_donation::Params,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::RefundDonation as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn init_config(
+    accounts: init_config::Accounts<Pubkey>,
+    params: init_config::Params,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::InitConfig as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn update_config(
+    accounts: update_config::Accounts<Pubkey>,
+    params: update_config::Params,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::UpdateConfig as u8, params)
 }
 
+#[allow(missing_docs)]
+pub fn buy_now(accounts: buy_now::Accounts<Pubkey>, params: buy_now::Params) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::BuyNow as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn commit_bid(
+    accounts: commit_bid::Accounts<Pubkey>,
+    params: commit_bid::Params,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::CommitBid as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn reveal_bid(
+    accounts: reveal_bid::Accounts<Pubkey>,
+    params: reveal_bid::Params,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::RevealBid as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn reclaim_bid(
+    accounts: reclaim_bid::Accounts<Pubkey>,
+    params: reclaim_bid::Params,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::ReclaimBid as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn claim_root_rewards(
+    accounts: claim_root_rewards::Accounts<Pubkey>,
+    params: claim_root_rewards::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        crate::ID,
+        ProgramInstruction::ClaimRootRewards as u8,
+        params,
+    )
+}
+
+#[allow(missing_docs)]
+pub fn unwrap_name(
+    accounts: unwrap_name::Accounts<Pubkey>,
+    params: unwrap_name::Params,
+) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::UnwrapName as u8, params)
+}
+
+#[allow(missing_docs)]
+pub fn wrap_name(accounts: wrap_name::Accounts<Pubkey>, params: wrap_name::Params) -> Instruction {
+    accounts.get_instruction(crate::ID, ProgramInstruction::WrapName as u8, params)
+}
 
 // #[allow(missing_docs)]
 // pub fn allocate_record(
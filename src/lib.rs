@@ -11,6 +11,12 @@ pub mod state;
 
 pub mod utils;
 
+/// Computation of the protocol fees
+pub mod fees;
+
+/// Minimum prices of names, by length and characters
+pub mod pricing;
+
 #[doc(hidden)]
 pub(crate) mod processor;
 
@@ -0,0 +1,99 @@
+use solana_program::program_error::ProgramError;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    error::AuctionError,
+    state::constants::{EMOJI_PREMIUM, LENGTH_PREMIUMS},
+};
+
+/// Computes the minimum price of `name` from a `base` price in lamports, with a premium for
+/// names of a few characters and for names made only of emojis
+pub fn get_name_price(name: &str, base: u64) -> Result<u64, ProgramError> {
+    let graphemes = name.graphemes(true).collect::<Vec<_>>();
+    let mut price = match graphemes.len().checked_sub(1) {
+        Some(i) if i < LENGTH_PREMIUMS.len() => base
+            .checked_mul(LENGTH_PREMIUMS[i])
+            .ok_or(AuctionError::NumericalOverflow)?,
+        _ => base,
+    };
+    if !graphemes.is_empty() && graphemes.iter().all(|g| is_emoji(g)) {
+        price = price
+            .checked_mul(EMOJI_PREMIUM)
+            .ok_or(AuctionError::NumericalOverflow)?;
+    }
+    Ok(price)
+}
+
+/// Whether a grapheme cluster is rendered as an emoji, including keycaps and flags
+fn is_emoji(grapheme: &str) -> bool {
+    grapheme.chars().any(|c| {
+        matches!(
+            c as u32,
+            0x1F000..=0x1FAFF // Pictographs, emoticons, transport, flags and supplements
+                | 0x2600..=0x27BF // Miscellaneous symbols and dingbats
+                | 0x2B00..=0x2BFF // Arrows and shapes such as ⭐
+                | 0xFE0F // Emoji presentation selector
+                | 0x20E3 // Combining keycap
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: u64 = 1_000;
+
+    #[test]
+    fn length_premiums() {
+        assert_eq!(get_name_price("a", BASE).unwrap(), 50_000);
+        assert_eq!(get_name_price("ab", BASE).unwrap(), 20_000);
+        assert_eq!(get_name_price("abc", BASE).unwrap(), 10_000);
+        assert_eq!(get_name_price("abcd", BASE).unwrap(), 5_000);
+        assert_eq!(get_name_price("abcde", BASE).unwrap(), BASE);
+        assert_eq!(get_name_price("", BASE).unwrap(), BASE);
+    }
+
+    #[test]
+    fn length_counts_graphemes() {
+        // An accented letter written with a combining mark is a single character
+        assert_eq!(get_name_price("e\u{301}", BASE).unwrap(), 50_000);
+        assert_eq!(get_name_price("café", BASE).unwrap(), 5_000);
+    }
+
+    #[test]
+    fn emoji_premium() {
+        assert_eq!(get_name_price("🔥", BASE).unwrap(), 100_000);
+        assert_eq!(get_name_price("🔥🚀🌙🎉🍕", BASE).unwrap(), 2 * BASE);
+        // A family is joined into a single grapheme
+        assert_eq!(
+            get_name_price("👨\u{200d}👩\u{200d}👧", BASE).unwrap(),
+            100_000
+        );
+    }
+
+    #[test]
+    fn mixed_names_have_no_emoji_premium() {
+        assert_eq!(get_name_price("a🔥", BASE).unwrap(), 20_000);
+        assert_eq!(get_name_price("fire🔥", BASE).unwrap(), BASE);
+    }
+
+    #[test]
+    fn price_overflow() {
+        assert!(get_name_price("a", u64::MAX).is_err());
+        assert!(get_name_price("🔥🚀🌙🎉🍕", u64::MAX).is_err());
+    }
+
+    #[test]
+    fn emoji_detection() {
+        assert!(is_emoji("😀"));
+        assert!(is_emoji("☕"));
+        assert!(is_emoji("⭐"));
+        // Keycaps and flags
+        assert!(is_emoji("1\u{fe0f}\u{20e3}"));
+        assert!(is_emoji("🇫🇷"));
+        assert!(!is_emoji("a"));
+        assert!(!is_emoji("1"));
+        assert!(!is_emoji("é"));
+    }
+}
@@ -9,8 +9,22 @@ use {
 
 use crate::instruction::ProgramInstruction;
 
+pub mod buy_now;
+pub mod claim_root_rewards;
+pub mod commit_bid;
+pub mod create_auction;
 pub mod crowd_root;
+pub mod delete_auction;
+pub mod init_config;
 pub mod create_root;
+pub mod place_bid;
+pub mod reclaim_bid;
+pub mod refund_donation;
+pub mod reveal_bid;
+pub mod settle_auction;
+pub mod unwrap_name;
+pub mod update_config;
+pub mod wrap_name;
 
 pub struct Processor {}
 
@@ -38,10 +52,74 @@ impl Processor {
                 create_root::process(program_id, accounts, params)?;
             }
             ProgramInstruction::CreateAuction => {
-
+                msg!("Instruction: create an auction");
+                let params = create_auction::Params::try_from_slice(instruction_data)?;
+                create_auction::process(program_id, accounts, params)?;
             }
             ProgramInstruction::DeleteAuction => {
-
+                msg!("Instruction: delete an auction");
+                let params = delete_auction::Params::try_from_slice(instruction_data)?;
+                delete_auction::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::PlaceBid => {
+                msg!("Instruction: place a bid");
+                let params = place_bid::Params::try_from_slice(instruction_data)?;
+                place_bid::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::SettleAuction => {
+                msg!("Instruction: settle an auction");
+                let params = settle_auction::Params::try_from_slice(instruction_data)?;
+                settle_auction::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::RefundDonation => {
+                msg!("Instruction: refund a donation");
+                let params = refund_donation::Params::try_from_slice(instruction_data)?;
+                refund_donation::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::InitConfig => {
+                msg!("Instruction: init the program config");
+                let params = init_config::Params::try_from_slice(instruction_data)?;
+                init_config::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::UpdateConfig => {
+                msg!("Instruction: update the program config");
+                let params = update_config::Params::try_from_slice(instruction_data)?;
+                update_config::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::BuyNow => {
+                msg!("Instruction: buy now");
+                let params = buy_now::Params::try_from_slice(instruction_data)?;
+                buy_now::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::CommitBid => {
+                msg!("Instruction: commit a sealed bid");
+                let params = commit_bid::Params::try_from_slice(instruction_data)?;
+                commit_bid::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::RevealBid => {
+                msg!("Instruction: reveal a sealed bid");
+                let params = reveal_bid::Params::try_from_slice(instruction_data)?;
+                reveal_bid::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::ReclaimBid => {
+                msg!("Instruction: reclaim a sealed bid");
+                let params = reclaim_bid::Params::try_from_slice(instruction_data)?;
+                reclaim_bid::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::ClaimRootRewards => {
+                msg!("Instruction: claim root rewards");
+                let params = claim_root_rewards::Params::try_from_slice(instruction_data)?;
+                claim_root_rewards::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::UnwrapName => {
+                msg!("Instruction: unwrap a tokenized name");
+                let params = unwrap_name::Params::try_from_slice(instruction_data)?;
+                unwrap_name::process(program_id, accounts, params)?;
+            }
+            ProgramInstruction::WrapName => {
+                msg!("Instruction: tokenize a name");
+                let params = wrap_name::Params::try_from_slice(instruction_data)?;
+                wrap_name::process(program_id, accounts, params)?;
             }
         }
 
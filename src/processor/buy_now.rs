@@ -0,0 +1,284 @@
+use bonfida_utils::checks::check_account_owner;
+use solana_program::{clock::Clock, msg, program::invoke, sysvar::Sysvar};
+
+use crate::{
+    central_state, cpi,
+    error::AuctionError,
+    fees::is_discount_metadata,
+    processor::settle_auction,
+    state::auction::{Auction, AuctionKind},
+    utils::{check_associated_token_account, get_vault_key, next_account_if},
+};
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_instruction, system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// The highest price the buyer accepts to pay, in lamports
+    pub max_price: u64,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The name service program account
+    pub web3_name_service: &'a T,
+
+    /// The name account being auctioned
+    #[cons(writable)]
+    pub name_account: &'a T,
+
+    /// The root name account of a subdomain, ignored for a top-level name
+    pub parent_name: &'a T,
+
+    /// The reward pool of the root of a subdomain, ignored for a top-level name
+    #[cons(writable)]
+    pub reward_pool: &'a T,
+
+    /// The auction account, closed by the instruction
+    #[cons(writable)]
+    pub auction: &'a T,
+
+    /// The account holding the payment of the auction
+    #[cons(writable)]
+    pub escrow: &'a T,
+
+    #[cons(writable, signer)]
+    /// The buyer, receiving the name
+    pub buyer: &'a T,
+
+    /// The seller, receiving the proceeds and the rent of the auction
+    #[cons(writable)]
+    pub seller: &'a T,
+
+    /// The program vault
+    #[cons(writable)]
+    pub vault: &'a T,
+
+    /// The central state, owning the escrowed name
+    pub central_state: &'a T,
+
+    /// The program config
+    pub config: &'a T,
+
+    /// The current highest bidder of an English auction, refunded from the escrow, or its
+    /// associated token account when the highest bid is in tokens
+    #[cons(writable)]
+    pub previous_bidder: Option<&'a T>,
+
+    /// The SPL token program, when the highest bid is in tokens
+    pub token_program: Option<&'a T>,
+
+    /// The associated token account of the escrow for the mint of the highest bid
+    #[cons(writable)]
+    pub escrow_token: Option<&'a T>,
+
+    /// The metadata of an NFT of the discount collection, when the seller claims the fee
+    /// discount
+    pub discount_metadata: Option<&'a T>,
+
+    /// The token account holding the discount NFT, owned by the seller
+    pub discount_token: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let mut accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            web3_name_service: next_account_info(accounts_iter)?,
+            name_account: next_account_info(accounts_iter)?,
+            parent_name: next_account_info(accounts_iter)?,
+            reward_pool: next_account_info(accounts_iter)?,
+            auction: next_account_info(accounts_iter)?,
+            escrow: next_account_info(accounts_iter)?,
+            buyer: next_account_info(accounts_iter)?,
+            seller: next_account_info(accounts_iter)?,
+            vault: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
+            previous_bidder: next_account_if(accounts_iter, |account| {
+                *account.key != spl_token::ID && !is_discount_metadata(account)
+            }),
+            token_program: next_account_if(accounts_iter, |account| *account.key == spl_token::ID),
+            escrow_token: None,
+            discount_metadata: None,
+            discount_token: None,
+        };
+        // Each group of optional accounts is passed together, or not at all
+        if accounts.token_program.is_some() {
+            accounts.escrow_token = next_account_info(accounts_iter).ok();
+        }
+        accounts.discount_metadata = next_account_if(accounts_iter, is_discount_metadata);
+        if accounts.discount_metadata.is_some() {
+            accounts.discount_token = next_account_info(accounts_iter).ok();
+        }
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.web3_name_service, &spl_name_service::ID)?;
+        check_account_key(accounts.vault, &get_vault_key().0)?;
+        check_account_key(accounts.central_state, &central_state::KEY)?;
+        if let Some(token_program) = accounts.token_program {
+            check_account_key(token_program, &spl_token::ID)?;
+        }
+
+        // Check owners
+        check_account_owner(accounts.auction, &crate::ID)?;
+
+        // Check signer
+        check_signer(accounts.buyer)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+
+    let (auction, name, outbid) = {
+        let mut auction_data = accounts.auction.data.borrow_mut();
+        let (auction, name) = Auction::from_buffer_mut(&mut auction_data)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        if now < auction.start_time {
+            msg!("The auction starts at {}", auction.start_time);
+            return Err(AuctionError::AuctionNotStarted.into());
+        }
+        if now >= auction.end_time {
+            msg!("The auction ended at {}", auction.end_time);
+            return Err(AuctionError::AuctionEnded.into());
+        }
+
+        let price = match auction.kind()? {
+            AuctionKind::Dutch => auction.current_price(now)?,
+            AuctionKind::English if auction.buy_now_price > 0 => {
+                if auction.has_bid() && auction.highest_bid >= auction.buy_now_price {
+                    msg!("A bid reached the buy now price");
+                    return Err(AuctionError::BuyNowUnavailable.into());
+                }
+                auction.buy_now_price
+            }
+            AuctionKind::English => {
+                msg!("The auction has no buy now price");
+                return Err(AuctionError::BuyNowUnavailable.into());
+            }
+            AuctionKind::Sealed => return Err(AuctionError::WrongAuctionKind.into()),
+        };
+        if params.max_price < price {
+            msg!("The current price is {} lamports", price);
+            return Err(AuctionError::BidTooLow.into());
+        }
+
+        let outbid = auction.has_bid().then_some(*auction);
+        auction.highest_bid = price;
+        auction.highest_bidder = *accounts.buyer.key;
+        auction.bid_mint = Pubkey::default();
+        auction.bid_token_amount = 0;
+        auction.referrer = Pubkey::default();
+        (*auction, name.to_owned(), outbid)
+    };
+
+    if let Some(outbid) = outbid {
+        let (auction_key, _) = Auction::find_key(&name, auction.parent());
+        let (escrow_key, escrow_seeds) = Auction::find_escrow_key(&auction_key);
+        check_account_key(accounts.escrow, &escrow_key)?;
+
+        msg!("Refund the highest bidder");
+        match outbid.bid_mint() {
+            Some(bid_mint) => {
+                let token_program = accounts
+                    .token_program
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                let escrow_token =
+                    check_associated_token_account(accounts.escrow_token, &escrow_key, bid_mint)?;
+                let previous_bidder = check_associated_token_account(
+                    accounts.previous_bidder,
+                    &outbid.highest_bidder,
+                    bid_mint,
+                )?;
+                cpi::transfer_tokens_signed(
+                    token_program,
+                    escrow_token,
+                    previous_bidder,
+                    accounts.escrow,
+                    outbid.bid_token_amount,
+                    &escrow_seeds,
+                )?;
+            }
+            None => {
+                let previous_bidder = accounts
+                    .previous_bidder
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                check_account_key(previous_bidder, &outbid.highest_bidder)?;
+                cpi::transfer_signed(
+                    accounts.system_program,
+                    accounts.escrow,
+                    previous_bidder,
+                    outbid.highest_bid,
+                    &escrow_seeds,
+                )?;
+            }
+        }
+    }
+
+    // The escrow key is checked by the settlement
+    invoke(
+        &system_instruction::transfer(accounts.buyer.key, accounts.escrow.key, auction.highest_bid),
+        &[
+            accounts.buyer.clone(),
+            accounts.escrow.clone(),
+            accounts.system_program.clone(),
+        ],
+    )?;
+
+    let settle_accounts = settle_auction::Accounts {
+        system_program: accounts.system_program,
+        web3_name_service: accounts.web3_name_service,
+        name_account: accounts.name_account,
+        parent_name: accounts.parent_name,
+        reward_pool: accounts.reward_pool,
+        auction: accounts.auction,
+        escrow: accounts.escrow,
+        winner: accounts.buyer,
+        seller: accounts.seller,
+        vault: accounts.vault,
+        central_state: accounts.central_state,
+        config: accounts.config,
+        winning_bid: None,
+        winning_deposit: None,
+        token_program: None,
+        escrow_token: None,
+        winner_token: None,
+        seller_token: None,
+        vault_token: None,
+        discount_metadata: accounts.discount_metadata,
+        discount_token: accounts.discount_token,
+        token_metadata_program: None,
+        nft_mint: None,
+        nft_metadata: None,
+        nft_master_edition: None,
+        nft_escrow: None,
+        nft_token: None,
+        nft_payer: None,
+        nft_token_program: None,
+        associated_token_program: None,
+        referrer: None,
+    };
+    settle_auction::settle(&settle_accounts, &auction, &name, 0)
+}
@@ -0,0 +1,132 @@
+use bonfida_utils::checks::check_account_owner;
+use solana_program::{msg, rent::Rent, sysvar::Sysvar};
+
+use crate::{
+    cpi,
+    error::AuctionError,
+    state::{contribution::Contribution, record_header::RecordHeader},
+};
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The account saving the fund state of the root
+    #[cons(writable)]
+    pub root_cord_account: &'a T,
+
+    /// The account recording the donations of the donor
+    #[cons(writable)]
+    pub contribution: &'a T,
+
+    /// The reward pool of the root
+    #[cons(writable)]
+    pub reward_pool: &'a T,
+
+    #[cons(writable, signer)]
+    /// The donor
+    pub donor: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            root_cord_account: next_account_info(accounts_iter)?,
+            contribution: next_account_info(accounts_iter)?,
+            reward_pool: next_account_info(accounts_iter)?,
+            donor: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+
+        // Check owners
+        check_account_owner(accounts.root_cord_account, &crate::ID)?;
+        check_account_owner(accounts.contribution, &crate::ID)?;
+
+        // Check signer
+        check_signer(accounts.donor)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], _params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+
+    let mut contribution = Contribution::unpack_from_slice(&accounts.contribution.data.borrow())?;
+    check_account_key(accounts.root_cord_account, &contribution.root_record)?;
+    check_account_key(accounts.donor, &contribution.donor)?;
+
+    let (contribution_key, _) = Contribution::find_key(
+        accounts.root_cord_account.key,
+        accounts.donor.key,
+        contribution.mint(),
+    );
+    check_account_key(accounts.contribution, &contribution_key)?;
+
+    let mut root_record_data = accounts.root_cord_account.data.borrow_mut();
+    let (root_record_header, _) = RecordHeader::from_buffer_mut(&mut root_record_data)?;
+
+    if root_record_header.amount < root_record_header.goal {
+        msg!("The root domain has not been created");
+        return Err(AuctionError::RootNotCreated.into());
+    }
+
+    let (reward_pool_key, reward_pool_seeds) =
+        RecordHeader::find_reward_pool_key(&root_record_header.root_name_key);
+    check_account_key(accounts.reward_pool, &reward_pool_key)?;
+
+    // Everything ever paid to the pool, its rent aside
+    let total_rewards = accounts
+        .reward_pool
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0))
+        .checked_add(root_record_header.claimed_rewards)
+        .ok_or(AuctionError::NumericalOverflow)?;
+    let entitled = (total_rewards as u128 * contribution.amount as u128
+        / root_record_header.amount as u128) as u64;
+    let claimable = entitled.saturating_sub(contribution.claimed);
+
+    if claimable == 0 {
+        msg!("Nothing to claim");
+        return Ok(());
+    }
+
+    msg!("Claim {} lamports", claimable);
+    cpi::transfer_signed(
+        accounts.system_program,
+        accounts.reward_pool,
+        accounts.donor,
+        claimable,
+        &reward_pool_seeds,
+    )?;
+
+    contribution.claimed += claimable;
+    contribution.pack_into_slice(&mut accounts.contribution.data.borrow_mut());
+    root_record_header.claimed_rewards += claimable;
+
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use bonfida_utils::checks::check_account_owner;
+use solana_program::{clock::Clock, msg, program::invoke, rent::Rent, sysvar::Sysvar};
+
+use crate::{
+    cpi,
+    error::AuctionError,
+    state::{
+        auction::{Auction, AuctionKind},
+        bid::Bid,
+    },
+};
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_instruction, system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// The 32 bytes of `utils::get_commitment(amount, salt, bidder)`
+    pub commitment: Vec<u8>,
+    /// The lamports locked with the bid, at least the amount of the bid
+    pub deposit: u64,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The sealed auction
+    pub auction: &'a T,
+
+    /// The bid account to create
+    #[cons(writable)]
+    pub bid: &'a T,
+
+    /// The account holding the deposit of the bid, see [`Bid::find_deposit_key`]
+    #[cons(writable)]
+    pub deposit: &'a T,
+
+    #[cons(writable, signer)]
+    /// The bidder
+    pub bidder: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            auction: next_account_info(accounts_iter)?,
+            bid: next_account_info(accounts_iter)?,
+            deposit: next_account_info(accounts_iter)?,
+            bidder: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+
+        // Check owners
+        check_account_owner(accounts.auction, &crate::ID)?;
+        check_account_owner(accounts.bid, &system_program::ID)?;
+        check_account_owner(accounts.deposit, &system_program::ID)?;
+
+        // Check signer
+        check_signer(accounts.bidder)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+
+    if params.commitment.len() != 32 {
+        msg!("The commitment must be 32 bytes long");
+        return Err(AuctionError::InvalidCommitment.into());
+    }
+    let mut commitment = [0; 32];
+    commitment.copy_from_slice(&params.commitment);
+
+    let auction_data = accounts.auction.data.borrow();
+    let (auction, name) = Auction::from_buffer(&auction_data)?;
+    auction.check_kind(AuctionKind::Sealed)?;
+
+    let (auction_key, _) = Auction::find_key(name, auction.parent());
+    check_account_key(accounts.auction, &auction_key)?;
+
+    let (bid_key, bid_seeds) = Bid::find_key(&auction_key, auction.start_time, accounts.bidder.key);
+    check_account_key(accounts.bid, &bid_key)?;
+
+    let (deposit_key, _) = Bid::find_deposit_key(&bid_key);
+    check_account_key(accounts.deposit, &deposit_key)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    if now < auction.start_time {
+        msg!("The auction starts at {}", auction.start_time);
+        return Err(AuctionError::AuctionNotStarted.into());
+    }
+    if now >= auction.end_time {
+        msg!("The auction ended at {}", auction.end_time);
+        return Err(AuctionError::AuctionEnded.into());
+    }
+
+    // The deposit bounds the revealed amount, so it must cover the reserve, and its account must
+    // be rent exempt
+    let min_deposit = auction.min_bid()?.max(Rent::get()?.minimum_balance(0));
+    if params.deposit < min_deposit {
+        msg!("The deposit must be at least {} lamports", min_deposit);
+        return Err(AuctionError::BidTooLow.into());
+    }
+
+    if accounts.bid.data_len() > 0 {
+        msg!("The bidder already committed a bid");
+        return Err(AuctionError::AlreadyInitialized.into());
+    }
+
+    cpi::create_program_account(
+        accounts.system_program,
+        accounts.bidder,
+        accounts.bid,
+        Bid::LEN,
+        &bid_seeds,
+    )?;
+
+    invoke(
+        &system_instruction::transfer(accounts.bidder.key, &deposit_key, params.deposit),
+        &[
+            accounts.bidder.clone(),
+            accounts.deposit.clone(),
+            accounts.system_program.clone(),
+        ],
+    )?;
+
+    let bid = Bid {
+        auction: auction_key,
+        auction_start: auction.start_time,
+        bidder: *accounts.bidder.key,
+        commitment,
+        deposit: params.deposit,
+        amount: 0,
+        revealed: false,
+    };
+    bid.pack_into_slice(&mut accounts.bid.data.borrow_mut());
+
+    Ok(())
+}
@@ -0,0 +1,300 @@
+use bonfida_utils::checks::check_account_owner;
+use solana_program::{clock::Clock, msg, program::invoke, rent::Rent, sysvar::Sysvar};
+use spl_name_service::state::NameRecordHeader;
+
+use crate::{
+    central_state, cpi,
+    error::AuctionError,
+    pricing::get_name_price,
+    state::{
+        auction::{Auction, AuctionKind, PriceDecay},
+        config::Config,
+        record_header::RecordHeader,
+    },
+    utils::{get_hashed_name, get_seeds_and_key},
+};
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_instruction, system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// The name to auction, the subdomain label if `root_name` is set
+    pub name: String,
+    /// The crowdfunded root domain of a subdomain auction
+    pub root_name: Option<String>,
+    /// The kind of auction
+    pub kind: AuctionKind,
+    /// The minimum amount of the first bid, or the floor price of a Dutch auction, in lamports
+    pub reserve_price: u64,
+    /// The minimum amount by which a bid must exceed the current highest bid, in lamports
+    pub min_increment: u64,
+    /// Number of seconds between the creation of the auction and the first accepted bid
+    pub start_delay: i64,
+    /// Number of seconds during which bids are accepted
+    pub duration: i64,
+    /// The price of a Dutch auction when it starts, in lamports
+    pub start_price: u64,
+    /// How the price of a Dutch auction decays to the floor
+    pub decay: PriceDecay,
+    /// Number of seconds in which the premium of an exponential Dutch auction halves
+    pub decay_period: i64,
+    /// Number of seconds after the end of a sealed auction during which bids are revealed
+    pub reveal_duration: i64,
+    /// Whether the winner of a sealed auction pays the second highest bid instead of their own
+    pub second_price: bool,
+    /// Number of seconds before the end of an English auction in which a bid extends it
+    pub extension_window: i64,
+    /// Maximum number of seconds by which an English auction can be extended
+    pub max_extension: i64,
+    /// The 32 bytes of `utils::get_commitment(reserve, salt, seller)` to hide the reserve
+    /// until settlement
+    pub reserve_commitment: Option<Vec<u8>>,
+    /// The instant purchase price of an English auction in lamports, 0 to disable it
+    pub buy_now_price: u64,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The name service program account
+    pub web3_name_service: &'a T,
+
+    /// The name account being auctioned, escrowed by the central state if it already exists
+    #[cons(writable)]
+    pub name_account: &'a T,
+
+    /// The auction account to create
+    #[cons(writable)]
+    pub auction: &'a T,
+
+    /// The account holding the bids of the auction
+    #[cons(writable)]
+    pub escrow: &'a T,
+
+    #[cons(writable, signer)]
+    /// The seller, also paying for the auction account
+    pub seller: &'a T,
+
+    /// The program config
+    pub config: &'a T,
+
+    /// The record of the crowdfunded root of a subdomain auction
+    pub root_record: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            web3_name_service: next_account_info(accounts_iter)?,
+            name_account: next_account_info(accounts_iter)?,
+            auction: next_account_info(accounts_iter)?,
+            escrow: next_account_info(accounts_iter)?,
+            seller: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
+            root_record: next_account_info(accounts_iter).ok(),
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.web3_name_service, &spl_name_service::ID)?;
+
+        // Check owners
+        check_account_owner(accounts.auction, &system_program::ID)?;
+        check_account_owner(accounts.escrow, &system_program::ID)?;
+
+        // Check signer
+        check_signer(accounts.seller)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    if params.start_delay < 0 || params.duration <= 0 {
+        msg!("The auction must last a positive amount of time");
+        return Err(AuctionError::InvalidDuration.into());
+    }
+    if params.kind == AuctionKind::Dutch {
+        if params.start_price < params.reserve_price {
+            msg!("The start price must be at least the floor price");
+            return Err(AuctionError::InvalidStartPrice.into());
+        }
+        if params.decay == PriceDecay::Exponential && params.decay_period <= 0 {
+            msg!("The decay period must be positive");
+            return Err(AuctionError::InvalidDuration.into());
+        }
+    }
+    if params.extension_window < 0 || params.max_extension < 0 {
+        msg!("The extension parameters cannot be negative");
+        return Err(AuctionError::InvalidDuration.into());
+    }
+    if params.buy_now_price > 0
+        && (params.kind != AuctionKind::English || params.buy_now_price < params.reserve_price)
+    {
+        msg!("The buy now price must be at least the reserve of an English auction");
+        return Err(AuctionError::InvalidBuyNowPrice.into());
+    }
+    if params.kind == AuctionKind::Dutch && params.reserve_commitment.is_some() {
+        msg!("The floor price of a Dutch auction is public");
+        return Err(AuctionError::WrongAuctionKind.into());
+    }
+    if matches!(&params.reserve_commitment, Some(c) if c.len() != 32) {
+        msg!("The reserve commitment must be 32 bytes long");
+        return Err(AuctionError::InvalidCommitment.into());
+    }
+    if params.kind == AuctionKind::Sealed && params.reveal_duration <= 0 {
+        msg!("The reveal window must last a positive amount of time");
+        return Err(AuctionError::InvalidDuration.into());
+    }
+
+    let accounts = Accounts::parse(accounts)?;
+
+    let parent = match &params.root_name {
+        Some(root_name) => {
+            let root_record = accounts
+                .root_record
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            let hashed_root_name = get_hashed_name(root_name);
+            let (root_record_key, _) =
+                get_seeds_and_key(&crate::ID, hashed_root_name.clone(), None, None);
+            check_account_key(root_record, &root_record_key)?;
+            check_account_owner(root_record, &crate::ID)?;
+
+            let root_record_data = root_record.data.borrow();
+            let (root_record_header, _) = RecordHeader::from_buffer(&root_record_data)?;
+            if root_record_header.amount < root_record_header.goal {
+                msg!("The root domain has not been created");
+                return Err(AuctionError::RootNotCreated.into());
+            }
+
+            let (root_name_key, _) =
+                get_seeds_and_key(accounts.web3_name_service.key, hashed_root_name, None, None);
+            Some(root_name_key)
+        }
+        None => None,
+    };
+
+    let (auction_key, seeds) = Auction::find_key(&params.name, parent.as_ref());
+    check_account_key(accounts.auction, &auction_key)?;
+
+    let (escrow_key, _) = Auction::find_escrow_key(&auction_key);
+    check_account_key(accounts.escrow, &escrow_key)?;
+
+    let (name_key, _) = get_seeds_and_key(
+        accounts.web3_name_service.key,
+        get_hashed_name(&params.name),
+        None,
+        parent.as_ref(),
+    );
+    check_account_key(accounts.name_account, &name_key)?;
+
+    if accounts.auction.data_len() > 0 {
+        msg!("The given auction account already exists.");
+        return Err(AuctionError::AlreadyInitialized.into());
+    }
+
+    let name_escrowed = accounts.name_account.data_len() > 0;
+    if name_escrowed {
+        msg!("Escrow the name with the central state");
+        cpi::transfer_name_account(
+            accounts.web3_name_service,
+            accounts.name_account,
+            accounts.seller,
+            &central_state::KEY,
+            &[],
+        )?;
+    } else {
+        // The winning bid pays for the creation of the name account
+        let config = Config::from_account_info(accounts.config)?;
+        let min_reserve = get_name_price(&params.name, config.min_name_price)?
+            .max(Rent::get()?.minimum_balance(NameRecordHeader::LEN));
+        if params.reserve_price < min_reserve {
+            msg!(
+                "The reserve price must be at least {} lamports",
+                min_reserve
+            );
+            return Err(AuctionError::ReserveTooLow.into());
+        }
+    }
+
+    cpi::create_program_account(
+        accounts.system_program,
+        accounts.seller,
+        accounts.auction,
+        Auction::space(&params.name),
+        &seeds,
+    )?;
+
+    // The escrow is kept rent exempt so that it can hold bids of any amount
+    let escrow_rent = Rent::get()?
+        .minimum_balance(0)
+        .saturating_sub(accounts.escrow.lamports());
+    if escrow_rent > 0 {
+        invoke(
+            &system_instruction::transfer(accounts.seller.key, &escrow_key, escrow_rent),
+            &[
+                accounts.seller.clone(),
+                accounts.escrow.clone(),
+                accounts.system_program.clone(),
+            ],
+        )?;
+    }
+
+    let start_time = Clock::get()?
+        .unix_timestamp
+        .checked_add(params.start_delay)
+        .ok_or(AuctionError::NumericalOverflow)?;
+    let end_time = start_time
+        .checked_add(params.duration)
+        .ok_or(AuctionError::NumericalOverflow)?;
+
+    let mut auction_data = accounts.auction.data.borrow_mut();
+    let auction = Auction::initialize(&mut auction_data, &params.name)?;
+    auction.kind = params.kind as u8;
+    auction.parent = parent.unwrap_or_default();
+    auction.name_escrowed = name_escrowed as u8;
+    auction.seller = *accounts.seller.key;
+    auction.reserve_price = params.reserve_price;
+    auction.min_increment = params.min_increment;
+    auction.start_time = start_time;
+    auction.end_time = end_time;
+    if let Some(commitment) = &params.reserve_commitment {
+        auction.reserve_commitment.copy_from_slice(commitment);
+    }
+    if params.kind == AuctionKind::English {
+        auction.extension_window = params.extension_window;
+        auction.max_extension = params.max_extension;
+        auction.buy_now_price = params.buy_now_price;
+    }
+    if params.kind == AuctionKind::Dutch {
+        auction.decay = params.decay as u8;
+        auction.start_price = params.start_price;
+        auction.decay_period = params.decay_period;
+    }
+    if params.kind == AuctionKind::Sealed {
+        auction.reveal_end_time = end_time
+            .checked_add(params.reveal_duration)
+            .ok_or(AuctionError::NumericalOverflow)?;
+        auction.second_price = params.second_price as u8;
+    }
+
+    Ok(())
+}
@@ -1,14 +1,16 @@
 
 use bonfida_utils::checks::check_account_owner;
 use solana_program::{
+    clock::Clock,
     msg,
+    program::invoke,
     rent::Rent,
     sysvar::Sysvar,
 };
 use spl_name_service::state::NameRecordHeader;
 
 use crate::{
-    central_state, state::{constants::CREATE_FEE, record_header::{write_data, RecordHeader}}, utils::{get_hashed_name, get_seeds_and_key}
+    central_state, error::AuctionError, fees::{get_fee, is_discount_metadata, split_referrer_fee}, state::{config::Config, contribution::Contribution, record_header::RecordHeader}, utils::{check_associated_token_account, check_referrer, get_hashed_name, get_lamport_value, next_account_if, get_seeds_and_key, get_vault_key}
 };
 
 use {
@@ -24,6 +26,7 @@ use {
         program_error::ProgramError,
         program_pack::Pack,
         pubkey::Pubkey,
+        system_instruction,
         system_program,
     },
 };
@@ -37,6 +40,8 @@ use crate::cpi;
 pub struct Params {
     pub root_name: String,
     pub add: u64,
+    /// The mint of the donated tokens, `add` is then an amount of tokens instead of lamports
+    pub mint: Option<Pubkey>,
 }
 
 #[derive(InstructionsAccount)]
@@ -72,12 +77,46 @@ pub struct Accounts<'a, T> {
 
     #[cons(writable)]
     pub create_fee_saver: &'a T,
+
+    /// The account recording the donations of the fee payer
+    #[cons(writable)]
+    pub contribution: &'a T,
+
+    /// The program config
+    pub config: &'a T,
+
+    /// The SPL token program, when donating tokens
+    pub token_program: Option<&'a T>,
+
+    /// The token account of the fee payer, when donating tokens
+    #[cons(writable)]
+    pub donor_token: Option<&'a T>,
+
+    /// The associated token account of the vault for the mint, when donating tokens
+    #[cons(writable)]
+    pub vault_token: Option<&'a T>,
+
+    /// The Pyth price feed of the donated tokens
+    pub token_feed: Option<&'a T>,
+
+    /// The Pyth price feed of SOL
+    pub sol_feed: Option<&'a T>,
+
+    /// The metadata of an NFT of the discount collection, when claiming the fee discount
+    pub discount_metadata: Option<&'a T>,
+
+    /// The token account holding the discount NFT, owned by the fee payer
+    pub discount_token: Option<&'a T>,
+
+    /// The referrer, or its token account when donating tokens, receiving a share of the fee
+    #[cons(writable)]
+    pub referrer: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
     pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
-        let accounts = Accounts {
+        let mut accounts = Accounts {
             system_program: next_account_info(accounts_iter)?,
             vault: next_account_info(accounts_iter)?,
             root_cord_account: next_account_info(accounts_iter)?,
@@ -89,11 +128,36 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             central_state: next_account_info(accounts_iter)?,
             rent_sysvar: next_account_info(accounts_iter)?,
             create_fee_saver: next_account_info(accounts_iter)?,
+            contribution: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
+            token_program: next_account_if(accounts_iter, |account| *account.key == spl_token::ID),
+            donor_token: None,
+            vault_token: None,
+            token_feed: None,
+            sol_feed: None,
+            discount_metadata: None,
+            discount_token: None,
+            referrer: None,
         };
+        // Each group of optional accounts is passed together, or not at all
+        if accounts.token_program.is_some() {
+            accounts.donor_token = next_account_info(accounts_iter).ok();
+            accounts.vault_token = next_account_info(accounts_iter).ok();
+            accounts.token_feed = next_account_info(accounts_iter).ok();
+            accounts.sol_feed = next_account_info(accounts_iter).ok();
+        }
+        accounts.discount_metadata = next_account_if(accounts_iter, is_discount_metadata);
+        if accounts.discount_metadata.is_some() {
+            accounts.discount_token = next_account_info(accounts_iter).ok();
+        }
+        accounts.referrer = next_account_info(accounts_iter).ok();
 
         // Check keys
         check_account_key(accounts.system_program, &system_program::ID)?;
-        // check_account_key(accounts.web3_name_service, &spl_name_service::ID)?;
+        check_account_key(accounts.vault, &get_vault_key().0)?;
+        // The central state owns the root so that it can sign for its subdomains
+        check_account_key(accounts.register_service_central, &central_state::KEY)?;
+        check_account_key(accounts.web3_name_service, &spl_name_service::ID)?;
 
         // Check owners
         check_account_owner(accounts.root_cord_account, &crate::ID)?;
@@ -107,13 +171,27 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
 
 
 pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
-    if params.add < 1000 {
+    let accounts = Accounts::parse(accounts)?;
+
+    let config = Config::from_account_info(accounts.config)?;
+    let mint = params.mint;
+
+    // Tokens count for their value in lamports
+    let value = match &mint {
+        Some(mint) => get_lamport_value(
+            mint,
+            params.add,
+            accounts.token_feed.ok_or(ProgramError::NotEnoughAccountKeys)?,
+            accounts.sol_feed.ok_or(ProgramError::NotEnoughAccountKeys)?,
+        )?,
+        None => params.add,
+    };
+
+    if value < config.min_donation {
         msg!("add amount is too small");
         return Err(ProgramError::InvalidArgument);
     }
 
-    let accounts = Accounts::parse(accounts)?;
-
     let hashed_name_account = get_hashed_name(&params.root_name);
     
     let (root_record_key, _) = get_seeds_and_key(
@@ -155,16 +233,101 @@ pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -
 
     check_account_key(accounts.create_fee_saver, &fee_saver_key)?;
 
+    let (contribution_key, contribution_seeds) =
+        Contribution::find_key(&root_record_key, accounts.fee_payer.key, mint.as_ref());
+    check_account_key(accounts.contribution, &contribution_key)?;
+
     let root_record_header = 
-        RecordHeader::unpack_from_slice(&root_record_account.data.borrow())?;
+        *RecordHeader::from_buffer(&root_record_account.data.borrow())?.0;
 
-    let new_amount = root_record_header.amount + params.add;
+    let clock = Clock::get()?;
+    if clock.unix_timestamp > root_record_header.deadline {
+        msg!("The campaign ended at {}", root_record_header.deadline);
+        return Err(AuctionError::CampaignEnded.into());
+    }
+
+    let new_amount = root_record_header.amount
+        .checked_add(value)
+        .ok_or(AuctionError::NumericalOverflow)?;
+
+    // The donation fee is paid on top of the donation and shared with the referrer
+    let fee = get_fee(
+        params.add,
+        config.donation_fee_bps,
+        accounts.fee_payer.key,
+        accounts.discount_metadata.zip(accounts.discount_token),
+    )?;
+    let (mut referrer_fee, mut vault_fee) = match accounts.referrer {
+        Some(referrer) => {
+            check_referrer(referrer, mint.as_ref())?;
+            split_referrer_fee(fee, config.referrer_fee_bps)?
+        }
+        None => (0, fee),
+    };
+    // A referrer that would not be rent exempt forgoes its share to the vault
+    if let Some(referrer) = accounts.referrer.filter(|_| mint.is_none()) {
+        let rent = Rent::get()?.minimum_balance(referrer.data_len());
+        if referrer.lamports().saturating_add(referrer_fee) < rent {
+            referrer_fee = 0;
+            vault_fee = fee;
+        }
+    }
+    let vault_amount = params.add
+        .checked_add(vault_fee)
+        .ok_or(AuctionError::NumericalOverflow)?;
+
+    match &mint {
+        Some(mint) => {
+            let token_program = accounts.token_program.ok_or(ProgramError::NotEnoughAccountKeys)?;
+            let donor_token = accounts.donor_token.ok_or(ProgramError::NotEnoughAccountKeys)?;
+            check_account_key(token_program, &spl_token::ID)?;
+            // The token program checks that the donor account holds the same mint
+            let vault_token =
+                check_associated_token_account(accounts.vault_token, accounts.vault.key, mint)?;
+
+            let transfers = std::iter::once((vault_token, vault_amount))
+                .chain(accounts.referrer.map(|referrer| (referrer, referrer_fee)));
+            for (destination, amount) in transfers.filter(|(_, amount)| *amount > 0) {
+                invoke(
+                    &spl_token::instruction::transfer(
+                        token_program.key,
+                        donor_token.key,
+                        destination.key,
+                        accounts.fee_payer.key,
+                        &[],
+                        amount,
+                    )?,
+                    &[
+                        donor_token.clone(),
+                        destination.clone(),
+                        accounts.fee_payer.clone(),
+                        token_program.clone(),
+                    ],
+                )?;
+            }
+        }
+        None => {
+            let transfers = std::iter::once((accounts.vault, vault_amount))
+                .chain(accounts.referrer.map(|referrer| (referrer, referrer_fee)));
+            for (destination, amount) in transfers.filter(|(_, amount)| *amount > 0) {
+                invoke(
+                    &system_instruction::transfer(
+                        accounts.fee_payer.key, destination.key, amount),
+                        &[
+                            accounts.fee_payer.clone(),
+                            destination.clone(),
+                            accounts.system_program.clone(),
+                        ],
+                    )?;
+            }
+        }
+    }
 
     let rent = Rent::get()?;
 
     let central_state_signer_seeds: &[&[u8]] = &[&crate::ID.to_bytes(), &[central_state::NONCE]];
 
-    if new_amount >= CREATE_FEE {
+    if new_amount >= root_record_header.goal {
         msg!("create root account");
         cpi::create_name_account(
             accounts.web3_name_service,
@@ -176,6 +339,9 @@ pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -
             rent.minimum_balance(NameRecordHeader::LEN),
             0,
             &seeds,
+            None,
+            None,
+            &[],
         )?;
 
         msg!("create root reverse account");
@@ -197,8 +363,46 @@ pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -
         }
     }
 
-    let bytes = new_amount.to_le_bytes();
-    write_data(root_record_account, &bytes, 32);
+    RecordHeader::from_buffer_mut(&mut root_record_account.data.borrow_mut())?.0.amount = new_amount;
+
+    let slot = clock.slot;
+    let contribution = if accounts.contribution.data_len() == 0 {
+        cpi::create_program_account(
+            accounts.system_program,
+            accounts.fee_payer,
+            accounts.contribution,
+            Contribution::LEN,
+            &contribution_seeds,
+        )?;
+        Contribution {
+            root_record: root_record_key,
+            donor: *accounts.fee_payer.key,
+            amount: value,
+            first_slot: slot,
+            last_slot: slot,
+            count: 1,
+            claimed: 0,
+            mint: mint.unwrap_or_default(),
+            token_amount: if mint.is_some() { params.add } else { 0 },
+        }
+    } else {
+        check_account_owner(accounts.contribution, &crate::ID)?;
+        let mut contribution =
+            Contribution::unpack_from_slice(&accounts.contribution.data.borrow())?;
+        contribution.amount = contribution.amount
+            .checked_add(value)
+            .ok_or(AuctionError::NumericalOverflow)?;
+        if mint.is_some() {
+            contribution.token_amount = contribution.token_amount
+                .checked_add(params.add)
+                .ok_or(AuctionError::NumericalOverflow)?;
+        }
+        contribution.last_slot = slot;
+        contribution.count += 1;
+        contribution
+    };
+
+    contribution.pack_into_slice(&mut accounts.contribution.data.borrow_mut());
 
     Ok(())
 }
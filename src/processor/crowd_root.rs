@@ -1,14 +1,14 @@
 
 use bonfida_utils::checks::check_account_owner;
 use solana_program::{
+    clock::Clock,
     msg,
     program::{invoke, invoke_signed},
     rent::Rent,
     sysvar::Sysvar,
 };
-use spl_name_service::state::{NameRecordHeader};
 
-use crate::{central_state, state::record_header::RecordHeader, utils::{get_hashed_name, get_seeds_and_key}};
+use crate::{central_state, error::AuctionError, pricing::get_name_price, state::{config::Config, constants::MAX_ROOT_NAME_LEN, record_header::RecordHeader, Tag}, utils::{get_hashed_name, get_seeds_and_key, get_vault_key}};
 
 use {
     bonfida_utils::{
@@ -21,7 +21,6 @@ use {
         account_info::{next_account_info, AccountInfo},
         entrypoint::ProgramResult,
         program_error::ProgramError,
-        program_pack::Pack,
         pubkey::Pubkey,
         system_instruction,
         system_program,
@@ -33,6 +32,10 @@ use {
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 pub struct Params {
     pub root_name: String,
+    /// Unix timestamp after which the campaign can be refunded
+    pub deadline: i64,
+    /// Amount of lamports to raise before the root domain is created
+    pub goal: u64,
 }
 
 #[derive(InstructionsAccount)]
@@ -54,6 +57,9 @@ pub struct Accounts<'a, T> {
 
     #[cons(writable)]
     pub create_fee_saver: &'a T,
+
+    /// The program config
+    pub config: &'a T,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -65,10 +71,12 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             root_cord_account: next_account_info(accounts_iter)?,
             fee_payer: next_account_info(accounts_iter)?,
             create_fee_saver: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
         };
 
         // Check keys
         check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.vault, &get_vault_key().0)?;
 
         // Check owners
         check_account_owner(accounts.root_cord_account, &system_program::ID)?;
@@ -81,8 +89,27 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
 }
 
 pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    if params.root_name.len() > MAX_ROOT_NAME_LEN {
+        msg!("The root name cannot exceed {} bytes", MAX_ROOT_NAME_LEN);
+        return Err(AuctionError::NameTooLong.into());
+    }
+
+    if params.deadline <= Clock::get()?.unix_timestamp {
+        msg!("The deadline must be in the future");
+        return Err(AuctionError::InvalidDeadline.into());
+    }
+
     let accounts = Accounts::parse(accounts)?;
 
+    let config = Config::from_account_info(accounts.config)?;
+
+    // Premium names priced above the upper bound cannot be crowdfunded
+    let min_goal = get_name_price(&params.root_name, config.min_root_goal)?;
+    if !(min_goal..=config.max_root_goal).contains(&params.goal) {
+        msg!("The goal must be between {} and {} lamports", min_goal, config.max_root_goal);
+        return Err(AuctionError::InvalidGoal.into());
+    }
+
     let (root_record_key, seeds) = get_seeds_and_key(
         &crate::ID, 
         get_hashed_name(&params.root_name), 
@@ -106,18 +133,17 @@ pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -
 
     check_account_key(accounts.create_fee_saver, &fee_saver_key)?;
 
-    if root_record_account.data.borrow().len() > 0 {
-        let root_record_header = 
-            RecordHeader::unpack_from_slice(&root_record_account.data.borrow())?;
-        if root_record_header.root_name_key != Pubkey::default() {
-            msg!("The given root account already exists.");
-            return Err(ProgramError::InvalidArgument);
-        }
+    if Tag::of(&root_record_account.data.borrow()) != Tag::Uninitialized {
+        msg!("The given root account already exists.");
+        return Err(AuctionError::AlreadyInitialized.into());
     }
 
     let rent = Rent::get()?;
-    let lamports = rent.minimum_balance(RecordHeader::LEN);
-    let create_fee = rent.minimum_balance(NameRecordHeader::LEN * 6);
+    let space = RecordHeader::space(&params.root_name);
+    let lamports = rent.minimum_balance(space);
+    // The creation fee pays the rent of the root name and its reverse lookup, it is not protocol
+    // revenue and gets no special discount, see `fees::get_fee`
+    let create_fee = config.root_creation_fee;
 
     if root_record_account.data.borrow().len() == 0 {
         invoke(
@@ -133,7 +159,7 @@ pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -
         invoke_signed(
             &system_instruction::allocate(
                 &root_record_key, 
-                RecordHeader::LEN as u64
+                space as u64
             ), 
             &[accounts.root_cord_account.clone(), accounts.system_program.clone()], 
             &[&seeds.chunks(32).collect::<Vec<&[u8]>>()],
@@ -157,13 +183,32 @@ pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -
     }
     
 
-    let init_state = RecordHeader {
-        root_name_key: root_record_key,
-        amount: 0,
-        name: params.root_name,
-    };
+    // Donations can be smaller than the rent exemption of the vault
+    let vault_rent = rent.minimum_balance(0).saturating_sub(accounts.vault.lamports());
+    if vault_rent > 0 {
+        invoke(
+            &system_instruction::transfer(
+                accounts.fee_payer.key, accounts.vault.key, vault_rent),
+                &[
+                    accounts.fee_payer.clone(),
+                    accounts.vault.clone(),
+                    accounts.system_program.clone(),
+                ],
+            )?;
+    }
 
-    init_state.pack_into_slice(&mut accounts.root_cord_account.data.borrow_mut());
+    let mut root_record_data = accounts.root_cord_account.data.borrow_mut();
+    let init_state = RecordHeader::initialize(&mut root_record_data, &params.root_name)?;
+    // Subdomain auctions know their root by its name account, see `find_reward_pool_key`
+    let (root_name_key, _) = get_seeds_and_key(
+        &spl_name_service::ID,
+        get_hashed_name(&params.root_name),
+        None,
+        None,
+    );
+    init_state.root_name_key = root_name_key;
+    init_state.deadline = params.deadline;
+    init_state.goal = params.goal;
 
     Ok(())
 }
\ No newline at end of file
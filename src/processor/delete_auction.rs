@@ -0,0 +1,179 @@
+use bonfida_utils::checks::check_account_owner;
+use solana_program::{clock::Clock, msg, sysvar::Sysvar};
+
+use crate::{
+    central_state, cpi,
+    error::AuctionError,
+    state::{
+        auction::{Auction, AuctionKind},
+        config::Config,
+    },
+    utils::{close_account, get_hashed_name, get_seeds_and_key},
+};
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The name service program account
+    pub web3_name_service: &'a T,
+
+    /// The name account being auctioned, returned to the seller if escrowed
+    #[cons(writable)]
+    pub name_account: &'a T,
+
+    /// The auction account to close
+    #[cons(writable)]
+    pub auction: &'a T,
+
+    /// The account holding the bids of the auction
+    #[cons(writable)]
+    pub escrow: &'a T,
+
+    /// The seller, receiving the rent of the auction
+    #[cons(writable)]
+    pub seller: &'a T,
+
+    /// The central state, owning the escrowed name
+    pub central_state: &'a T,
+
+    /// The program config
+    pub config: &'a T,
+
+    #[cons(signer)]
+    /// The seller or the admin of the program config
+    pub authority: &'a T,
+
+    /// The current highest bidder, refunded from the escrow
+    #[cons(writable)]
+    pub highest_bidder: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            web3_name_service: next_account_info(accounts_iter)?,
+            name_account: next_account_info(accounts_iter)?,
+            auction: next_account_info(accounts_iter)?,
+            escrow: next_account_info(accounts_iter)?,
+            seller: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
+            authority: next_account_info(accounts_iter)?,
+            highest_bidder: next_account_info(accounts_iter).ok(),
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.web3_name_service, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &central_state::KEY)?;
+
+        // Check owners
+        check_account_owner(accounts.auction, &crate::ID)?;
+
+        // Check signer
+        check_signer(accounts.authority)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], _params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+
+    let config = Config::from_account_info(accounts.config)?;
+    let (auction, name) = {
+        let auction_data = accounts.auction.data.borrow();
+        let (auction, name) = Auction::from_buffer(&auction_data)?;
+        (*auction, name.to_owned())
+    };
+
+    let (auction_key, _) = Auction::find_key(&name, auction.parent());
+    check_account_key(accounts.auction, &auction_key)?;
+
+    let (escrow_key, escrow_seeds) = Auction::find_escrow_key(&auction_key);
+    check_account_key(accounts.escrow, &escrow_key)?;
+
+    let (name_key, _) = get_seeds_and_key(
+        accounts.web3_name_service.key,
+        get_hashed_name(&name),
+        None,
+        auction.parent(),
+    );
+    check_account_key(accounts.name_account, &name_key)?;
+    check_account_key(accounts.seller, &auction.seller)?;
+
+    if *accounts.authority.key != auction.seller && *accounts.authority.key != config.admin {
+        msg!("Only the seller or the admin can cancel an auction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let started = Clock::get()?.unix_timestamp >= auction.start_time;
+    // Sealed bids are unknown until they are revealed
+    if started && (auction.has_bid() || auction.kind()? == AuctionKind::Sealed) {
+        msg!("The auction has live bids");
+        return Err(AuctionError::AuctionHasBids.into());
+    }
+
+    if auction.has_bid() {
+        let highest_bidder = accounts
+            .highest_bidder
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        check_account_key(highest_bidder, &auction.highest_bidder)?;
+
+        msg!("Refund the highest bidder");
+        cpi::transfer_signed(
+            accounts.system_program,
+            accounts.escrow,
+            highest_bidder,
+            auction.highest_bid,
+            &escrow_seeds,
+        )?;
+    }
+
+    if auction.is_name_escrowed() {
+        msg!("Return the name to the seller");
+        let central_state_signer_seeds: &[&[u8]] =
+            &[&crate::ID.to_bytes(), &[central_state::NONCE]];
+        cpi::transfer_name_account(
+            accounts.web3_name_service,
+            accounts.name_account,
+            accounts.central_state,
+            &auction.seller,
+            &[central_state_signer_seeds],
+        )?;
+    }
+
+    cpi::transfer_signed(
+        accounts.system_program,
+        accounts.escrow,
+        accounts.seller,
+        accounts.escrow.lamports(),
+        &escrow_seeds,
+    )?;
+
+    close_account(accounts.auction, accounts.seller)?;
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use bonfida_utils::checks::check_account_owner;
+use solana_program::{program::invoke, rent::Rent, system_instruction, sysvar::Sysvar};
+
+use crate::{
+    cpi,
+    state::{config::Config, constants::AUTHORITY},
+    utils::get_vault_key,
+};
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    pub admin: Pubkey,
+    pub min_root_goal: u64,
+    pub max_root_goal: u64,
+    pub min_donation: u64,
+    pub root_creation_fee: u64,
+    pub auction_fee_bps: u64,
+    pub unrevealed_forfeit_bps: u64,
+    pub root_share_bps: u64,
+    pub donation_fee_bps: u64,
+    pub referrer_fee_bps: u64,
+    pub min_name_price: u64,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The config account to create
+    #[cons(writable)]
+    pub config: &'a T,
+
+    #[cons(writable, signer)]
+    /// The program authority, paying for the config account
+    pub authority: &'a T,
+
+    /// The program vault, funded to rent exemption
+    #[cons(writable)]
+    pub vault: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
+            authority: next_account_info(accounts_iter)?,
+            vault: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.config, &Config::find_key().0)?;
+        check_account_key(accounts.authority, &AUTHORITY)?;
+        check_account_key(accounts.vault, &get_vault_key().0)?;
+
+        // Check owners
+        check_account_owner(accounts.config, &system_program::ID)?;
+
+        // Check signer
+        check_signer(accounts.authority)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+
+    let config = Config {
+        admin: params.admin,
+        min_root_goal: params.min_root_goal,
+        max_root_goal: params.max_root_goal,
+        min_donation: params.min_donation,
+        root_creation_fee: params.root_creation_fee,
+        auction_fee_bps: params.auction_fee_bps,
+        unrevealed_forfeit_bps: params.unrevealed_forfeit_bps,
+        root_share_bps: params.root_share_bps,
+        donation_fee_bps: params.donation_fee_bps,
+        referrer_fee_bps: params.referrer_fee_bps,
+        min_name_price: params.min_name_price,
+    };
+    config.check()?;
+
+    let (_, seeds) = Config::find_key();
+    cpi::create_program_account(
+        accounts.system_program,
+        accounts.authority,
+        accounts.config,
+        Config::LEN,
+        &seeds,
+    )?;
+
+    config.pack_into_slice(&mut accounts.config.data.borrow_mut());
+
+    // Fees and forfeits can be smaller than the rent exemption of the vault
+    let vault_rent = Rent::get()?
+        .minimum_balance(0)
+        .saturating_sub(accounts.vault.lamports());
+    if vault_rent > 0 {
+        invoke(
+            &system_instruction::transfer(accounts.authority.key, accounts.vault.key, vault_rent),
+            &[
+                accounts.authority.clone(),
+                accounts.vault.clone(),
+                accounts.system_program.clone(),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,275 @@
+use bonfida_utils::checks::check_account_owner;
+use solana_program::{clock::Clock, msg, program::invoke, sysvar::Sysvar};
+
+use crate::{
+    cpi,
+    error::AuctionError,
+    state::auction::{Auction, AuctionKind},
+    utils::{
+        check_associated_token_account, check_referrer_key, get_lamport_value, next_account_if,
+    },
+};
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_instruction, system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// The amount of the bid, in lamports or in tokens of `mint`
+    pub amount: u64,
+    /// The mint of a bid paid in tokens, valued in lamports through Pyth
+    pub mint: Option<Pubkey>,
+    /// The referrer of the bid, sharing the fees of the auction if the bid wins
+    pub referrer: Option<Pubkey>,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The auction account
+    #[cons(writable)]
+    pub auction: &'a T,
+
+    /// The account holding the bids of the auction
+    #[cons(writable)]
+    pub escrow: &'a T,
+
+    #[cons(writable, signer)]
+    /// The bidder
+    pub bidder: &'a T,
+
+    /// The current highest bidder, refunded from the escrow, or its associated token account
+    /// when the highest bid is in tokens
+    #[cons(writable)]
+    pub previous_bidder: Option<&'a T>,
+
+    /// The SPL token program, when the bid or the highest bid is in tokens
+    pub token_program: Option<&'a T>,
+
+    /// The associated token account of the escrow for the mint of the bid, or of the highest
+    /// bid when bidding in lamports
+    #[cons(writable)]
+    pub escrow_token: Option<&'a T>,
+
+    /// The token account of the bidder, when bidding in tokens
+    #[cons(writable)]
+    pub bidder_token: Option<&'a T>,
+
+    /// The Pyth price feed of the mint of the bid
+    pub token_feed: Option<&'a T>,
+
+    /// The Pyth price feed of SOL
+    pub sol_feed: Option<&'a T>,
+
+    /// The associated token account of the escrow for the mint of the highest bid, when it
+    /// differs from the mint of the bid
+    #[cons(writable)]
+    pub previous_escrow_token: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            auction: next_account_info(accounts_iter)?,
+            escrow: next_account_info(accounts_iter)?,
+            bidder: next_account_info(accounts_iter)?,
+            previous_bidder: next_account_if(accounts_iter, |account| {
+                *account.key != spl_token::ID
+            }),
+            token_program: next_account_info(accounts_iter).ok(),
+            escrow_token: next_account_info(accounts_iter).ok(),
+            bidder_token: next_account_info(accounts_iter).ok(),
+            token_feed: next_account_info(accounts_iter).ok(),
+            sol_feed: next_account_info(accounts_iter).ok(),
+            previous_escrow_token: next_account_info(accounts_iter).ok(),
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        if let Some(token_program) = accounts.token_program {
+            check_account_key(token_program, &spl_token::ID)?;
+        }
+
+        // Check owners
+        check_account_owner(accounts.auction, &crate::ID)?;
+
+        // Check signer
+        check_signer(accounts.bidder)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+
+    let mut auction_data = accounts.auction.data.borrow_mut();
+    let (auction, name) = Auction::from_buffer_mut(&mut auction_data)?;
+    auction.check_kind(AuctionKind::English)?;
+
+    let (auction_key, _) = Auction::find_key(name, auction.parent());
+    check_account_key(accounts.auction, &auction_key)?;
+
+    let (escrow_key, escrow_seeds) = Auction::find_escrow_key(&auction_key);
+    check_account_key(accounts.escrow, &escrow_key)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    if now < auction.start_time {
+        msg!("The auction starts at {}", auction.start_time);
+        return Err(AuctionError::AuctionNotStarted.into());
+    }
+    if now >= auction.end_time {
+        msg!("The auction ended at {}", auction.end_time);
+        return Err(AuctionError::AuctionEnded.into());
+    }
+
+    if let Some(referrer) = &params.referrer {
+        check_referrer_key(referrer)?;
+    }
+
+    // Bids in tokens compete through their value in lamports
+    let value = match &params.mint {
+        Some(mint) => {
+            if !auction.accepts_tokens() {
+                msg!("Bids in tokens are only accepted for escrowed top-level names");
+                return Err(AuctionError::UnsupportedMint.into());
+            }
+            get_lamport_value(
+                mint,
+                params.amount,
+                accounts
+                    .token_feed
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?,
+                accounts
+                    .sol_feed
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?,
+            )?
+        }
+        None => params.amount,
+    };
+
+    let min_bid = auction.min_bid()?;
+    if value < min_bid {
+        msg!("The bid must be worth at least {} lamports", min_bid);
+        return Err(AuctionError::BidTooLow.into());
+    }
+
+    match &params.mint {
+        Some(mint) => {
+            let token_program = accounts
+                .token_program
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            let escrow_token =
+                check_associated_token_account(accounts.escrow_token, &escrow_key, mint)?;
+            let bidder_token = accounts
+                .bidder_token
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+            invoke(
+                &spl_token::instruction::transfer(
+                    token_program.key,
+                    bidder_token.key,
+                    escrow_token.key,
+                    accounts.bidder.key,
+                    &[],
+                    params.amount,
+                )?,
+                &[
+                    bidder_token.clone(),
+                    escrow_token.clone(),
+                    accounts.bidder.clone(),
+                    token_program.clone(),
+                ],
+            )?;
+        }
+        None => {
+            invoke(
+                &system_instruction::transfer(accounts.bidder.key, &escrow_key, params.amount),
+                &[
+                    accounts.bidder.clone(),
+                    accounts.escrow.clone(),
+                    accounts.system_program.clone(),
+                ],
+            )?;
+        }
+    }
+
+    if auction.has_bid() {
+        msg!("Refund the previous bidder");
+        match auction.bid_mint().copied() {
+            Some(bid_mint) => {
+                let token_program = accounts
+                    .token_program
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                let escrow_token = match &params.mint {
+                    Some(mint) if *mint != bid_mint => accounts.previous_escrow_token,
+                    _ => accounts.escrow_token,
+                };
+                let escrow_token =
+                    check_associated_token_account(escrow_token, &escrow_key, &bid_mint)?;
+                let previous_bidder = check_associated_token_account(
+                    accounts.previous_bidder,
+                    &auction.highest_bidder,
+                    &bid_mint,
+                )?;
+                cpi::transfer_tokens_signed(
+                    token_program,
+                    escrow_token,
+                    previous_bidder,
+                    accounts.escrow,
+                    auction.bid_token_amount,
+                    &escrow_seeds,
+                )?;
+            }
+            None => {
+                let previous_bidder = accounts
+                    .previous_bidder
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                check_account_key(previous_bidder, &auction.highest_bidder)?;
+                cpi::transfer_signed(
+                    accounts.system_program,
+                    accounts.escrow,
+                    previous_bidder,
+                    auction.highest_bid,
+                    &escrow_seeds,
+                )?;
+            }
+        }
+    }
+
+    auction.highest_bid = value;
+    auction.highest_bidder = *accounts.bidder.key;
+    auction.bid_mint = params.mint.unwrap_or_default();
+    auction.referrer = params.referrer.unwrap_or_default();
+    auction.bid_token_amount = if params.mint.is_some() {
+        params.amount
+    } else {
+        0
+    };
+
+    if auction.extend(now)? {
+        msg!(
+            "Extension #{}: the auction now ends at {}",
+            auction.extension_count,
+            auction.end_time
+        );
+    }
+
+    Ok(())
+}
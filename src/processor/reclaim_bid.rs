@@ -0,0 +1,144 @@
+use bonfida_utils::checks::check_account_owner;
+use solana_program::{clock::Clock, msg, sysvar::Sysvar};
+
+use crate::{
+    cpi,
+    error::AuctionError,
+    state::{auction::Auction, bid::Bid, config::Config},
+    utils::{close_account, get_vault_key},
+};
+
+use {
+    bonfida_utils::{checks::check_account_key, BorshSize, InstructionsAccount},
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The sealed auction, possibly already settled
+    pub auction: &'a T,
+
+    /// The bid account, closed by the instruction
+    #[cons(writable)]
+    pub bid: &'a T,
+
+    /// The account holding the deposit of the bid
+    #[cons(writable)]
+    pub deposit: &'a T,
+
+    /// The bidder, receiving the deposit and the rent of the bid
+    #[cons(writable)]
+    pub bidder: &'a T,
+
+    /// The program vault, receiving the forfeited share of unrevealed bids
+    #[cons(writable)]
+    pub vault: &'a T,
+
+    /// The program config
+    pub config: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            auction: next_account_info(accounts_iter)?,
+            bid: next_account_info(accounts_iter)?,
+            deposit: next_account_info(accounts_iter)?,
+            bidder: next_account_info(accounts_iter)?,
+            vault: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.vault, &get_vault_key().0)?;
+
+        // Check owners
+        check_account_owner(accounts.bid, &crate::ID)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], _params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+
+    let config = Config::from_account_info(accounts.config)?;
+    let bid = Bid::unpack_from_slice(&accounts.bid.data.borrow())?;
+    check_account_key(accounts.auction, &bid.auction)?;
+    check_account_key(accounts.bidder, &bid.bidder)?;
+    check_account_key(
+        accounts.bid,
+        &Bid::find_key(&bid.auction, bid.auction_start, &bid.bidder).0,
+    )?;
+    let (deposit_key, deposit_seeds) = Bid::find_deposit_key(accounts.bid.key);
+    check_account_key(accounts.deposit, &deposit_key)?;
+
+    // Once the auction is settled its account is closed, or reused by a later auction of the
+    // same name, and every bid can be reclaimed
+    let auction_data = accounts.auction.data.borrow();
+    let auction = match accounts.auction.data_len() {
+        0 => None,
+        _ => {
+            check_account_owner(accounts.auction, &crate::ID)?;
+            Some(Auction::from_buffer(&auction_data)?.0)
+        }
+    };
+    if let Some(auction) = auction.filter(|auction| auction.start_time == bid.auction_start) {
+        if Clock::get()?.unix_timestamp < auction.reveal_end_time {
+            msg!("Bids can be reclaimed from {}", auction.reveal_end_time);
+            return Err(AuctionError::BidLocked.into());
+        }
+        if auction.has_bid() && auction.highest_bidder == bid.bidder {
+            msg!("The winning bid is collected when the auction is settled");
+            return Err(AuctionError::BidLocked.into());
+        }
+    }
+
+    if !bid.revealed {
+        let forfeit = bid
+            .deposit
+            .checked_mul(config.unrevealed_forfeit_bps)
+            .ok_or(AuctionError::NumericalOverflow)?
+            / 10_000;
+        msg!(
+            "The bid was not revealed, {} lamports are forfeited",
+            forfeit
+        );
+        cpi::transfer_signed(
+            accounts.system_program,
+            accounts.deposit,
+            accounts.vault,
+            forfeit,
+            &deposit_seeds,
+        )?;
+    }
+
+    cpi::transfer_signed(
+        accounts.system_program,
+        accounts.deposit,
+        accounts.bidder,
+        accounts.deposit.lamports(),
+        &deposit_seeds,
+    )?;
+
+    close_account(accounts.bid, accounts.bidder)?;
+
+    Ok(())
+}
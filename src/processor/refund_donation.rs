@@ -0,0 +1,160 @@
+use bonfida_utils::checks::check_account_owner;
+use solana_program::{clock::Clock, msg, sysvar::Sysvar};
+
+use crate::{
+    cpi,
+    error::AuctionError,
+    state::{contribution::Contribution, record_header::RecordHeader},
+    utils::{check_associated_token_account, close_account, get_vault_key},
+};
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The account holding the donations
+    #[cons(writable)]
+    pub vault: &'a T,
+
+    /// The account saving the fund state of the root
+    #[cons(writable)]
+    pub root_cord_account: &'a T,
+
+    /// The account recording the donations of the donor, closed by the instruction
+    #[cons(writable)]
+    pub contribution: &'a T,
+
+    #[cons(writable, signer)]
+    /// The donor
+    pub donor: &'a T,
+
+    /// The SPL token program, when refunding tokens
+    pub token_program: Option<&'a T>,
+
+    /// The associated token account of the vault for the donated mint
+    #[cons(writable)]
+    pub vault_token: Option<&'a T>,
+
+    /// The token account of the donor, receiving the refund
+    #[cons(writable)]
+    pub donor_token: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            vault: next_account_info(accounts_iter)?,
+            root_cord_account: next_account_info(accounts_iter)?,
+            contribution: next_account_info(accounts_iter)?,
+            donor: next_account_info(accounts_iter)?,
+            token_program: next_account_info(accounts_iter).ok(),
+            vault_token: next_account_info(accounts_iter).ok(),
+            donor_token: next_account_info(accounts_iter).ok(),
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.vault, &get_vault_key().0)?;
+
+        // Check owners
+        check_account_owner(accounts.root_cord_account, &crate::ID)?;
+        check_account_owner(accounts.contribution, &crate::ID)?;
+
+        // Check signer
+        check_signer(accounts.donor)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], _params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+
+    let contribution = Contribution::unpack_from_slice(&accounts.contribution.data.borrow())?;
+    check_account_key(accounts.root_cord_account, &contribution.root_record)?;
+    check_account_key(accounts.donor, &contribution.donor)?;
+
+    let (contribution_key, _) = Contribution::find_key(
+        accounts.root_cord_account.key,
+        accounts.donor.key,
+        contribution.mint(),
+    );
+    check_account_key(accounts.contribution, &contribution_key)?;
+
+    let mut root_record_data = accounts.root_cord_account.data.borrow_mut();
+    let (root_record_header, _) = RecordHeader::from_buffer_mut(&mut root_record_data)?;
+
+    if Clock::get()?.unix_timestamp <= root_record_header.deadline {
+        msg!("The campaign ends at {}", root_record_header.deadline);
+        return Err(AuctionError::CampaignActive.into());
+    }
+    if root_record_header.amount >= root_record_header.goal {
+        msg!("The root domain has been created");
+        return Err(AuctionError::CampaignFunded.into());
+    }
+
+    let (_, vault_seeds) = get_vault_key();
+    match contribution.mint() {
+        Some(mint) => {
+            let token_program = accounts
+                .token_program
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            let donor_token = accounts
+                .donor_token
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            check_account_key(token_program, &spl_token::ID)?;
+            let vault_token =
+                check_associated_token_account(accounts.vault_token, accounts.vault.key, mint)?;
+
+            cpi::transfer_tokens_signed(
+                token_program,
+                vault_token,
+                donor_token,
+                accounts.vault,
+                contribution.token_amount,
+                &vault_seeds,
+            )?;
+        }
+        None => {
+            cpi::transfer_signed(
+                accounts.system_program,
+                accounts.vault,
+                accounts.donor,
+                contribution.amount,
+                &vault_seeds,
+            )?;
+        }
+    }
+
+    let new_amount = root_record_header
+        .amount
+        .checked_sub(contribution.amount)
+        .ok_or(AuctionError::NumericalOverflow)?;
+    root_record_header.amount = new_amount;
+
+    close_account(accounts.contribution, accounts.donor)?;
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use bonfida_utils::checks::check_account_owner;
+use solana_program::{clock::Clock, msg, sysvar::Sysvar};
+
+use crate::{
+    error::AuctionError,
+    state::{
+        auction::{Auction, AuctionKind},
+        bid::Bid,
+    },
+    utils::get_commitment,
+};
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// The amount of the bid, in lamports
+    pub amount: u64,
+    /// The salt used to compute the commitment
+    pub salt: Vec<u8>,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The sealed auction
+    #[cons(writable)]
+    pub auction: &'a T,
+
+    /// The bid account
+    #[cons(writable)]
+    pub bid: &'a T,
+
+    #[cons(signer)]
+    /// The bidder
+    pub bidder: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            auction: next_account_info(accounts_iter)?,
+            bid: next_account_info(accounts_iter)?,
+            bidder: next_account_info(accounts_iter)?,
+        };
+
+        // Check owners
+        check_account_owner(accounts.auction, &crate::ID)?;
+        check_account_owner(accounts.bid, &crate::ID)?;
+
+        // Check signer
+        check_signer(accounts.bidder)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+
+    let mut auction_data = accounts.auction.data.borrow_mut();
+    let (auction, name) = Auction::from_buffer_mut(&mut auction_data)?;
+    auction.check_kind(AuctionKind::Sealed)?;
+
+    let (auction_key, _) = Auction::find_key(name, auction.parent());
+    check_account_key(accounts.auction, &auction_key)?;
+
+    let mut bid = Bid::unpack_from_slice(&accounts.bid.data.borrow())?;
+    check_account_key(
+        accounts.bid,
+        &Bid::find_key(&auction_key, auction.start_time, accounts.bidder.key).0,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    if now < auction.end_time {
+        msg!("Bids can be revealed from {}", auction.end_time);
+        return Err(AuctionError::AuctionInProgress.into());
+    }
+    if now >= auction.reveal_end_time {
+        msg!("The reveal window ended at {}", auction.reveal_end_time);
+        return Err(AuctionError::RevealEnded.into());
+    }
+
+    if bid.revealed {
+        msg!("The bid has already been revealed");
+        return Err(AuctionError::AlreadyInitialized.into());
+    }
+    if get_commitment(params.amount, &params.salt, accounts.bidder.key) != bid.commitment {
+        msg!("The amount and salt do not match the commitment");
+        return Err(AuctionError::InvalidCommitment.into());
+    }
+
+    bid.revealed = true;
+    bid.amount = params.amount;
+    bid.pack_into_slice(&mut accounts.bid.data.borrow_mut());
+
+    // A revealed bid above its deposit or below the reserve is refunded but cannot win
+    let valid = params.amount <= bid.deposit && params.amount >= auction.reserve_price.max(1);
+    if valid && (!auction.has_bid() || params.amount > auction.highest_bid) {
+        msg!("New highest bid");
+        auction.second_highest_bid = auction.highest_bid;
+        auction.highest_bid = params.amount;
+        auction.highest_bidder = *accounts.bidder.key;
+    } else if valid && params.amount > auction.second_highest_bid {
+        auction.second_highest_bid = params.amount;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,719 @@
+use bonfida_utils::checks::check_account_owner;
+use solana_program::{clock::Clock, msg, rent::Rent, sysvar::Sysvar};
+use spl_name_service::state::NameRecordHeader;
+
+use crate::{
+    central_state, cpi,
+    error::AuctionError,
+    fees::{get_fee, is_discount_metadata, split_referrer_fee},
+    processor::wrap_name,
+    state::{
+        auction::{Auction, AuctionKind},
+        bid::Bid,
+        config::Config,
+        constants::RESERVE_REVEAL_PERIOD,
+        record_header::RecordHeader,
+    },
+    utils::{
+        check_associated_token_account, check_referrer, close_account, get_commitment,
+        get_hashed_name, get_seeds_and_key, get_vault_key, next_account_if,
+    },
+};
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// The hidden reserve price, revealed by the seller
+    pub reserve: Option<u64>,
+    /// The salt used to commit to the hidden reserve price
+    pub reserve_salt: Vec<u8>,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The name service program account
+    pub web3_name_service: &'a T,
+
+    /// The name account being auctioned
+    #[cons(writable)]
+    pub name_account: &'a T,
+
+    /// The root name account of a subdomain, ignored for a top-level name
+    pub parent_name: &'a T,
+
+    /// The reward pool of the root of a subdomain, ignored for a top-level name
+    #[cons(writable)]
+    pub reward_pool: &'a T,
+
+    /// The auction account, closed by the instruction
+    #[cons(writable)]
+    pub auction: &'a T,
+
+    /// The account holding the bids of the auction
+    #[cons(writable)]
+    pub escrow: &'a T,
+
+    /// The highest bidder, receiving the name or the refund of a bid below the reserve
+    #[cons(writable)]
+    pub winner: &'a T,
+
+    /// The seller, receiving the proceeds and the rent of the auction, signing to reveal a
+    /// hidden reserve
+    #[cons(writable)]
+    pub seller: &'a T,
+
+    /// The program vault
+    #[cons(writable)]
+    pub vault: &'a T,
+
+    /// The central state, owning the escrowed name
+    pub central_state: &'a T,
+
+    /// The program config
+    pub config: &'a T,
+
+    /// The bid of the winner of a sealed auction, closed to the winner
+    #[cons(writable)]
+    pub winning_bid: Option<&'a T>,
+
+    /// The deposit of the winning bid, paying for the name, the rest going back to the winner
+    #[cons(writable)]
+    pub winning_deposit: Option<&'a T>,
+
+    /// The SPL token program, when the highest bid is in tokens
+    pub token_program: Option<&'a T>,
+
+    /// The associated token account of the escrow for the mint of the highest bid
+    #[cons(writable)]
+    pub escrow_token: Option<&'a T>,
+
+    /// The associated token account of the highest bidder, refunded below the reserve
+    #[cons(writable)]
+    pub winner_token: Option<&'a T>,
+
+    /// The associated token account of the seller, receiving the proceeds
+    #[cons(writable)]
+    pub seller_token: Option<&'a T>,
+
+    /// The associated token account of the vault, receiving the fee
+    #[cons(writable)]
+    pub vault_token: Option<&'a T>,
+
+    /// The metadata of an NFT of the discount collection, when the seller claims the fee
+    /// discount
+    pub discount_metadata: Option<&'a T>,
+
+    /// The token account holding the discount NFT, owned by the seller
+    pub discount_token: Option<&'a T>,
+
+    /// The token metadata program, when the winner tokenizes the name
+    pub token_metadata_program: Option<&'a T>,
+
+    /// The mint of the NFT of the name, see [`crate::utils::get_nft_mint_key`]
+    #[cons(writable)]
+    pub nft_mint: Option<&'a T>,
+
+    /// The metadata account of the NFT
+    #[cons(writable)]
+    pub nft_metadata: Option<&'a T>,
+
+    /// The master edition account of the NFT
+    #[cons(writable)]
+    pub nft_master_edition: Option<&'a T>,
+
+    /// The associated token account of the central state, holding the NFT of an unwrapped name
+    #[cons(writable)]
+    pub nft_escrow: Option<&'a T>,
+
+    /// The associated token account of the winner for the NFT
+    #[cons(writable)]
+    pub nft_token: Option<&'a T>,
+
+    /// The winner, signing to tokenize the name and paying for the NFT accounts
+    #[cons(writable, signer)]
+    pub nft_payer: Option<&'a T>,
+
+    /// The SPL token program, minting the NFT
+    pub nft_token_program: Option<&'a T>,
+
+    /// The associated token account program
+    pub associated_token_program: Option<&'a T>,
+
+    /// The referrer of the highest bid, or its associated token account when the highest bid
+    /// is in tokens, receiving a share of the fee; any whitelisted referrer if the bid has none
+    #[cons(writable)]
+    pub referrer: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let mut accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            web3_name_service: next_account_info(accounts_iter)?,
+            name_account: next_account_info(accounts_iter)?,
+            parent_name: next_account_info(accounts_iter)?,
+            reward_pool: next_account_info(accounts_iter)?,
+            auction: next_account_info(accounts_iter)?,
+            escrow: next_account_info(accounts_iter)?,
+            winner: next_account_info(accounts_iter)?,
+            seller: next_account_info(accounts_iter)?,
+            vault: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
+            winning_bid: next_account_if(accounts_iter, |account| *account.owner == crate::ID),
+            winning_deposit: None,
+            token_program: None,
+            escrow_token: None,
+            winner_token: None,
+            seller_token: None,
+            vault_token: None,
+            discount_metadata: None,
+            discount_token: None,
+            token_metadata_program: None,
+            nft_mint: None,
+            nft_metadata: None,
+            nft_master_edition: None,
+            nft_escrow: None,
+            nft_token: None,
+            nft_payer: None,
+            nft_token_program: None,
+            associated_token_program: None,
+            referrer: None,
+        };
+        // Each group of optional accounts is passed together, or not at all
+        if accounts.winning_bid.is_some() {
+            accounts.winning_deposit = next_account_info(accounts_iter).ok();
+        }
+        accounts.token_program =
+            next_account_if(accounts_iter, |account| *account.key == spl_token::ID);
+        if accounts.token_program.is_some() {
+            accounts.escrow_token = next_account_info(accounts_iter).ok();
+            accounts.winner_token = next_account_info(accounts_iter).ok();
+            accounts.seller_token = next_account_info(accounts_iter).ok();
+            accounts.vault_token = next_account_info(accounts_iter).ok();
+        }
+        accounts.discount_metadata = next_account_if(accounts_iter, is_discount_metadata);
+        if accounts.discount_metadata.is_some() {
+            accounts.discount_token = next_account_info(accounts_iter).ok();
+        }
+        accounts.token_metadata_program = next_account_if(accounts_iter, |account| {
+            *account.key == mpl_token_metadata::ID
+        });
+        if accounts.token_metadata_program.is_some() {
+            accounts.nft_mint = next_account_info(accounts_iter).ok();
+            accounts.nft_metadata = next_account_info(accounts_iter).ok();
+            accounts.nft_master_edition = next_account_info(accounts_iter).ok();
+            accounts.nft_escrow = next_account_info(accounts_iter).ok();
+            accounts.nft_token = next_account_info(accounts_iter).ok();
+            accounts.nft_payer = next_account_info(accounts_iter).ok();
+            accounts.nft_token_program = next_account_info(accounts_iter).ok();
+            accounts.associated_token_program = next_account_info(accounts_iter).ok();
+        }
+        accounts.referrer = next_account_info(accounts_iter).ok();
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.web3_name_service, &spl_name_service::ID)?;
+        check_account_key(accounts.vault, &get_vault_key().0)?;
+        check_account_key(accounts.central_state, &central_state::KEY)?;
+        if let Some(token_program) = accounts.token_program {
+            check_account_key(token_program, &spl_token::ID)?;
+        }
+        if let Some(token_program) = accounts.nft_token_program {
+            check_account_key(token_program, &spl_token::ID)?;
+        }
+        if let Some(associated_token_program) = accounts.associated_token_program {
+            check_account_key(associated_token_program, &spl_associated_token_account::ID)?;
+        }
+
+        // Check signer
+        if let Some(nft_payer) = accounts.nft_payer {
+            check_signer(nft_payer)?;
+        }
+
+        // Check owners
+        check_account_owner(accounts.auction, &crate::ID)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+
+    let (auction, name) = {
+        let auction_data = accounts.auction.data.borrow();
+        let (auction, name) = Auction::from_buffer(&auction_data)?;
+        (*auction, name.to_owned())
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+    if now < auction.settle_time() {
+        msg!("The auction ends at {}", auction.settle_time());
+        return Err(AuctionError::AuctionInProgress.into());
+    }
+
+    let reserve = match params.reserve {
+        Some(reserve) => {
+            check_signer(accounts.seller)?;
+            if get_commitment(reserve, &params.reserve_salt, &auction.seller)
+                != auction.reserve_commitment
+            {
+                msg!("The reserve and salt do not match the commitment");
+                return Err(AuctionError::InvalidCommitment.into());
+            }
+            reserve
+        }
+        None if auction.has_hidden_reserve() => {
+            // The seller has a head start to reveal the reserve, past it the reserve is void
+            let deadline = auction
+                .settle_time()
+                .checked_add(RESERVE_REVEAL_PERIOD)
+                .ok_or(AuctionError::NumericalOverflow)?;
+            if now < deadline {
+                msg!("Only the seller can settle the auction until {}", deadline);
+                return Err(AuctionError::AuctionInProgress.into());
+            }
+            0
+        }
+        None => 0,
+    };
+
+    settle(&accounts, &auction, &name, reserve)
+}
+
+/// Hands the name to the highest bidder, or back to the seller if there is no bid above
+/// `reserve`, pays out the escrow and closes the auction
+pub(crate) fn settle(
+    accounts: &Accounts<AccountInfo>,
+    auction: &Auction,
+    name: &str,
+    reserve: u64,
+) -> ProgramResult {
+    let config = Config::from_account_info(accounts.config)?;
+
+    let (auction_key, _) = Auction::find_key(name, auction.parent());
+    check_account_key(accounts.auction, &auction_key)?;
+
+    let (escrow_key, escrow_seeds) = Auction::find_escrow_key(&auction_key);
+    check_account_key(accounts.escrow, &escrow_key)?;
+
+    let hashed_name = get_hashed_name(name);
+    let (name_key, _) = get_seeds_and_key(
+        accounts.web3_name_service.key,
+        hashed_name.clone(),
+        None,
+        auction.parent(),
+    );
+    check_account_key(accounts.name_account, &name_key)?;
+    if let Some(parent) = auction.parent() {
+        check_account_key(accounts.parent_name, parent)?;
+    }
+    check_account_key(accounts.seller, &auction.seller)?;
+
+    // A name registered by someone else while the auction ran cannot be sold anymore
+    let name_taken = !auction.is_name_escrowed() && accounts.name_account.data_len() > 0;
+
+    let mut auction = *auction;
+    if auction.has_bid() && (auction.highest_bid < reserve || name_taken) {
+        if name_taken {
+            msg!("No sale, the name has been registered elsewhere");
+        } else {
+            msg!("No sale, the highest bid is below the reserve");
+        }
+        if let Some(bid_mint) = auction.bid_mint() {
+            let winner_token = check_associated_token_account(
+                accounts.winner_token,
+                &auction.highest_bidder,
+                bid_mint,
+            )?;
+            transfer_escrow_tokens(
+                accounts,
+                &auction,
+                &escrow_key,
+                winner_token,
+                auction.bid_token_amount,
+                &escrow_seeds,
+            )?;
+        } else if auction.kind()? != AuctionKind::Sealed {
+            check_account_key(accounts.winner, &auction.highest_bidder)?;
+            cpi::transfer_signed(
+                accounts.system_program,
+                accounts.escrow,
+                accounts.winner,
+                auction.highest_bid,
+                &escrow_seeds,
+            )?;
+        }
+        // A sealed bid stays in its deposit, reclaimed once the auction is closed
+        auction.highest_bidder = Pubkey::default();
+    }
+
+    let mut winning_bid = None;
+    if auction.kind()? == AuctionKind::Sealed && auction.has_bid() {
+        let bid = accounts
+            .winning_bid
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        check_account_key(
+            bid,
+            &Bid::find_key(&auction_key, auction.start_time, &auction.highest_bidder).0,
+        )?;
+        check_account_owner(bid, &crate::ID)?;
+
+        let deposit = accounts
+            .winning_deposit
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let (deposit_key, deposit_seeds) = Bid::find_deposit_key(bid.key);
+        check_account_key(deposit, &deposit_key)?;
+
+        check_account_key(accounts.winner, &auction.highest_bidder)?;
+
+        let price = auction.clearing_price().max(reserve);
+        msg!("Collect {} lamports from the winning bid", price);
+        cpi::transfer_signed(
+            accounts.system_program,
+            deposit,
+            accounts.escrow,
+            price,
+            &deposit_seeds,
+        )?;
+        // Under second-price settlement the winner gets the rest of the deposit back
+        cpi::transfer_signed(
+            accounts.system_program,
+            deposit,
+            accounts.winner,
+            deposit.lamports(),
+            &deposit_seeds,
+        )?;
+        auction.highest_bid = price;
+        winning_bid = Some(bid);
+    }
+
+    let central_state_signer_seeds: &[&[u8]] = &[&crate::ID.to_bytes(), &[central_state::NONCE]];
+    // A tokenized name is kept by the central state, the NFT stands for its ownership
+    let tokenize = accounts.token_metadata_program.is_some() && auction.has_bid();
+
+    if !auction.has_bid() {
+        msg!("No bid, the name goes back to the seller");
+        if auction.is_name_escrowed() {
+            cpi::transfer_name_account(
+                accounts.web3_name_service,
+                accounts.name_account,
+                accounts.central_state,
+                &auction.seller,
+                &[central_state_signer_seeds],
+            )?;
+        }
+    } else if auction.is_name_escrowed() {
+        check_account_key(accounts.winner, &auction.highest_bidder)?;
+
+        if !tokenize {
+            msg!("Transfer the name to the winner");
+            cpi::transfer_name_account(
+                accounts.web3_name_service,
+                accounts.name_account,
+                accounts.central_state,
+                &auction.highest_bidder,
+                &[central_state_signer_seeds],
+            )?;
+        }
+
+        if let Some(bid_mint) = auction.bid_mint() {
+            // Only escrowed top-level names take bids in tokens, there is no root share
+            let fee = get_fee(
+                auction.bid_token_amount,
+                config.auction_fee_bps,
+                &auction.seller,
+                accounts.discount_metadata.zip(accounts.discount_token),
+            )?;
+            pay_fee(accounts, &auction, &config, &escrow_key, fee, &escrow_seeds)?;
+
+            let seller_token =
+                check_associated_token_account(accounts.seller_token, &auction.seller, bid_mint)?;
+            transfer_escrow_tokens(
+                accounts,
+                &auction,
+                &escrow_key,
+                seller_token,
+                auction.bid_token_amount - fee,
+                &escrow_seeds,
+            )?;
+        } else {
+            let fee = get_fee(
+                auction.highest_bid,
+                config.auction_fee_bps,
+                &auction.seller,
+                accounts.discount_metadata.zip(accounts.discount_token),
+            )?;
+            pay_fee(accounts, &auction, &config, &escrow_key, fee, &escrow_seeds)?;
+
+            let proceeds = auction
+                .highest_bid
+                .checked_sub(fee)
+                .ok_or(AuctionError::NumericalOverflow)?;
+            pay_root_share(accounts, &auction, &config, proceeds, &escrow_seeds)?;
+        }
+    } else {
+        check_account_key(accounts.winner, &auction.highest_bidder)?;
+
+        msg!("Create the name for the winner");
+        let name_rent = Rent::get()?.minimum_balance(NameRecordHeader::LEN);
+        cpi::create_name_account(
+            accounts.web3_name_service,
+            accounts.system_program,
+            accounts.name_account,
+            accounts.escrow,
+            if tokenize {
+                accounts.central_state
+            } else {
+                accounts.winner
+            },
+            hashed_name,
+            name_rent,
+            0,
+            &escrow_seeds,
+            auction.parent().map(|_| accounts.parent_name),
+            auction.parent().map(|_| accounts.central_state),
+            central_state_signer_seeds,
+        )?;
+
+        // The name was not owned by the seller, the proceeds go to the vault
+        let proceeds = auction
+            .highest_bid
+            .checked_sub(name_rent)
+            .ok_or(AuctionError::NumericalOverflow)?;
+        let proceeds =
+            proceeds - pay_root_share(accounts, &auction, &config, proceeds, &escrow_seeds)?;
+        pay_fee(
+            accounts,
+            &auction,
+            &config,
+            &escrow_key,
+            proceeds,
+            &escrow_seeds,
+        )?;
+    }
+
+    if tokenize {
+        tokenize_name(accounts, &auction, name)?;
+    }
+
+    // The seller gets the rest of the escrow, including its rent
+    cpi::transfer_signed(
+        accounts.system_program,
+        accounts.escrow,
+        accounts.seller,
+        accounts.escrow.lamports(),
+        &escrow_seeds,
+    )?;
+
+    // Program-owned accounts are debited directly, which must come after the last CPI or the
+    // runtime rejects the CPI as unbalanced
+    if let Some(winning_bid) = winning_bid {
+        close_account(winning_bid, accounts.winner)?;
+    }
+    close_account(accounts.auction, accounts.seller)?;
+
+    Ok(())
+}
+
+/// Pays a protocol fee out of the escrow to the vault, shared with the referrer of the highest
+/// bid, or else with the referrer of the settlement
+fn pay_fee(
+    accounts: &Accounts<AccountInfo>,
+    auction: &Auction,
+    config: &Config,
+    escrow_key: &Pubkey,
+    fee: u64,
+    escrow_seeds: &[u8],
+) -> ProgramResult {
+    let referrer = match auction.referrer() {
+        Some(referrer) => {
+            let account = accounts
+                .referrer
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            match auction.bid_mint() {
+                Some(bid_mint) => {
+                    check_associated_token_account(Some(account), referrer, bid_mint)?;
+                }
+                None => check_account_key(account, referrer)?,
+            }
+            Some(account)
+        }
+        None => {
+            if let Some(account) = accounts.referrer {
+                check_referrer(account, auction.bid_mint())?;
+            }
+            accounts.referrer
+        }
+    };
+    let (mut referrer_fee, mut vault_fee) = match referrer {
+        Some(_) => split_referrer_fee(fee, config.referrer_fee_bps)?,
+        None => (0, fee),
+    };
+
+    match auction.bid_mint() {
+        Some(bid_mint) => {
+            let vault_token =
+                check_associated_token_account(accounts.vault_token, accounts.vault.key, bid_mint)?;
+            transfer_escrow_tokens(
+                accounts,
+                auction,
+                escrow_key,
+                vault_token,
+                vault_fee,
+                escrow_seeds,
+            )?;
+            if let Some(referrer) = referrer.filter(|_| referrer_fee > 0) {
+                msg!("Pay {} tokens to the referrer", referrer_fee);
+                transfer_escrow_tokens(
+                    accounts,
+                    auction,
+                    escrow_key,
+                    referrer,
+                    referrer_fee,
+                    escrow_seeds,
+                )?;
+            }
+        }
+        None => {
+            // A referrer that would not be rent exempt forgoes its share to the vault
+            if let Some(referrer) = referrer {
+                let rent = Rent::get()?.minimum_balance(referrer.data_len());
+                if referrer.lamports().saturating_add(referrer_fee) < rent {
+                    referrer_fee = 0;
+                    vault_fee = fee;
+                }
+            }
+            cpi::transfer_signed(
+                accounts.system_program,
+                accounts.escrow,
+                accounts.vault,
+                vault_fee,
+                escrow_seeds,
+            )?;
+            if let Some(referrer) = referrer.filter(|_| referrer_fee > 0) {
+                msg!("Pay {} lamports to the referrer", referrer_fee);
+                cpi::transfer_signed(
+                    accounts.system_program,
+                    accounts.escrow,
+                    referrer,
+                    referrer_fee,
+                    escrow_seeds,
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Hands the NFT of the name, kept by the central state, to the winner
+fn tokenize_name(accounts: &Accounts<AccountInfo>, auction: &Auction, name: &str) -> ProgramResult {
+    let missing = || ProgramError::NotEnoughAccountKeys;
+    let nft_payer = accounts.nft_payer.ok_or_else(missing)?;
+    check_account_key(nft_payer, &auction.highest_bidder)?;
+
+    let wrap_accounts = wrap_name::Accounts {
+        system_program: accounts.system_program,
+        token_program: accounts.nft_token_program.ok_or_else(missing)?,
+        associated_token_program: accounts.associated_token_program.ok_or_else(missing)?,
+        token_metadata_program: accounts.token_metadata_program.ok_or_else(missing)?,
+        web3_name_service: accounts.web3_name_service,
+        name_account: accounts.name_account,
+        nft_mint: accounts.nft_mint.ok_or_else(missing)?,
+        nft_metadata: accounts.nft_metadata.ok_or_else(missing)?,
+        nft_master_edition: accounts.nft_master_edition.ok_or_else(missing)?,
+        nft_escrow: accounts.nft_escrow.ok_or_else(missing)?,
+        nft_token: accounts.nft_token.ok_or_else(missing)?,
+        owner: nft_payer,
+        central_state: accounts.central_state,
+    };
+    wrap_name::deliver_nft(&wrap_accounts, name)
+}
+
+/// Sends `amount` tokens of the highest bid out of the escrow
+fn transfer_escrow_tokens<'a>(
+    accounts: &Accounts<AccountInfo<'a>>,
+    auction: &Auction,
+    escrow_key: &Pubkey,
+    destination: &AccountInfo<'a>,
+    amount: u64,
+    escrow_seeds: &[u8],
+) -> ProgramResult {
+    let token_program = accounts
+        .token_program
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let escrow_token =
+        check_associated_token_account(accounts.escrow_token, escrow_key, &auction.bid_mint)?;
+    cpi::transfer_tokens_signed(
+        token_program,
+        escrow_token,
+        destination,
+        accounts.escrow,
+        amount,
+        escrow_seeds,
+    )
+}
+
+/// Sends the root share of `proceeds` to the reward pool of the root of a subdomain,
+/// returning the lamports taken out of the escrow
+fn pay_root_share(
+    accounts: &Accounts<AccountInfo>,
+    auction: &Auction,
+    config: &Config,
+    proceeds: u64,
+    escrow_seeds: &[u8],
+) -> Result<u64, ProgramError> {
+    let parent = match auction.parent() {
+        Some(parent) => parent,
+        None => return Ok(0),
+    };
+    let (reward_pool_key, _) = RecordHeader::find_reward_pool_key(parent);
+    check_account_key(accounts.reward_pool, &reward_pool_key)?;
+
+    let share = proceeds
+        .checked_mul(config.root_share_bps)
+        .ok_or(AuctionError::NumericalOverflow)?
+        / 10_000;
+    // The pool is kept rent exempt, its rent is not distributed to the donors
+    let pool_rent = Rent::get()?
+        .minimum_balance(0)
+        .saturating_sub(accounts.reward_pool.lamports());
+    let deposit = share
+        .checked_add(pool_rent)
+        .ok_or(AuctionError::NumericalOverflow)?;
+    if share == 0 || deposit > proceeds {
+        return Ok(0);
+    }
+
+    msg!("Send {} lamports to the root reward pool", share);
+    cpi::transfer_signed(
+        accounts.system_program,
+        accounts.escrow,
+        accounts.reward_pool,
+        deposit,
+        escrow_seeds,
+    )?;
+
+    Ok(deposit)
+}
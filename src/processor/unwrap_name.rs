@@ -0,0 +1,157 @@
+use bonfida_utils::checks::check_account_owner;
+use solana_program::{msg, program::invoke};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+
+use crate::{central_state, cpi, utils::get_nft_mint_key};
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The SPL token program
+    pub token_program: &'a T,
+
+    /// The associated token account program
+    pub associated_token_program: &'a T,
+
+    /// The name service program account
+    pub web3_name_service: &'a T,
+
+    /// The tokenized name account, owned by the central state
+    #[cons(writable)]
+    pub name_account: &'a T,
+
+    /// The mint of the NFT of the name
+    pub nft_mint: &'a T,
+
+    /// The token account holding the NFT
+    #[cons(writable)]
+    pub nft_token: &'a T,
+
+    /// The associated token account of the central state, escrowing the NFT until the name is
+    /// wrapped again
+    #[cons(writable)]
+    pub nft_escrow: &'a T,
+
+    #[cons(writable, signer)]
+    /// The holder of the NFT, receiving the name
+    pub holder: &'a T,
+
+    /// The central state
+    pub central_state: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            token_program: next_account_info(accounts_iter)?,
+            associated_token_program: next_account_info(accounts_iter)?,
+            web3_name_service: next_account_info(accounts_iter)?,
+            name_account: next_account_info(accounts_iter)?,
+            nft_mint: next_account_info(accounts_iter)?,
+            nft_token: next_account_info(accounts_iter)?,
+            nft_escrow: next_account_info(accounts_iter)?,
+            holder: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.token_program, &spl_token::ID)?;
+        check_account_key(
+            accounts.associated_token_program,
+            &spl_associated_token_account::ID,
+        )?;
+        check_account_key(accounts.web3_name_service, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &central_state::KEY)?;
+
+        // Check owners
+        check_account_owner(accounts.name_account, accounts.web3_name_service.key)?;
+        check_account_owner(accounts.nft_mint, &spl_token::ID)?;
+
+        // Check signer
+        check_signer(accounts.holder)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], _params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+
+    let (mint_key, _) = get_nft_mint_key(accounts.name_account.key);
+    check_account_key(accounts.nft_mint, &mint_key)?;
+    check_account_key(
+        accounts.nft_escrow,
+        &get_associated_token_address(&central_state::KEY, &mint_key),
+    )?;
+
+    invoke(
+        &create_associated_token_account_idempotent(
+            accounts.holder.key,
+            &central_state::KEY,
+            &mint_key,
+            accounts.token_program.key,
+        ),
+        &[
+            accounts.holder.clone(),
+            accounts.nft_escrow.clone(),
+            accounts.central_state.clone(),
+            accounts.nft_mint.clone(),
+            accounts.system_program.clone(),
+            accounts.token_program.clone(),
+            accounts.associated_token_program.clone(),
+        ],
+    )?;
+
+    // The NFT cannot be minted again, it is kept until the name is wrapped again
+    msg!("Escrow the NFT with the central state");
+    invoke(
+        &spl_token::instruction::transfer(
+            accounts.token_program.key,
+            accounts.nft_token.key,
+            accounts.nft_escrow.key,
+            accounts.holder.key,
+            &[],
+            1,
+        )?,
+        &[
+            accounts.nft_token.clone(),
+            accounts.nft_escrow.clone(),
+            accounts.holder.clone(),
+            accounts.token_program.clone(),
+        ],
+    )?;
+
+    msg!("Transfer the name to the holder");
+    cpi::transfer_name_account(
+        accounts.web3_name_service,
+        accounts.name_account,
+        accounts.central_state,
+        accounts.holder.key,
+        &[&[&crate::ID.to_bytes(), &[central_state::NONCE]]],
+    )
+}
@@ -0,0 +1,83 @@
+use crate::state::config::Config;
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    pub admin: Pubkey,
+    pub min_root_goal: u64,
+    pub max_root_goal: u64,
+    pub min_donation: u64,
+    pub root_creation_fee: u64,
+    pub auction_fee_bps: u64,
+    pub unrevealed_forfeit_bps: u64,
+    pub root_share_bps: u64,
+    pub donation_fee_bps: u64,
+    pub referrer_fee_bps: u64,
+    pub min_name_price: u64,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The config account
+    #[cons(writable)]
+    pub config: &'a T,
+
+    #[cons(signer)]
+    /// The current admin of the config
+    pub admin: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            config: next_account_info(accounts_iter)?,
+            admin: next_account_info(accounts_iter)?,
+        };
+
+        // Check signer
+        check_signer(accounts.admin)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+
+    let current = Config::from_account_info(accounts.config)?;
+    check_account_key(accounts.admin, &current.admin)?;
+
+    let config = Config {
+        admin: params.admin,
+        min_root_goal: params.min_root_goal,
+        max_root_goal: params.max_root_goal,
+        min_donation: params.min_donation,
+        root_creation_fee: params.root_creation_fee,
+        auction_fee_bps: params.auction_fee_bps,
+        unrevealed_forfeit_bps: params.unrevealed_forfeit_bps,
+        root_share_bps: params.root_share_bps,
+        donation_fee_bps: params.donation_fee_bps,
+        referrer_fee_bps: params.referrer_fee_bps,
+        min_name_price: params.min_name_price,
+    };
+    config.check()?;
+
+    config.pack_into_slice(&mut accounts.config.data.borrow_mut());
+
+    Ok(())
+}
@@ -0,0 +1,299 @@
+use bonfida_utils::checks::check_account_owner;
+use mpl_token_metadata::{
+    accounts::{MasterEdition, Metadata},
+    instructions::{CreateMasterEditionV3CpiBuilder, CreateMetadataAccountV3CpiBuilder},
+    types::DataV2,
+};
+use solana_program::{
+    msg,
+    program::{invoke, invoke_signed},
+};
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+use spl_name_service::state::NameRecordHeader;
+use spl_token::state::Mint;
+
+use crate::{
+    central_state, cpi,
+    error::AuctionError,
+    utils::{get_hashed_name, get_nft_mint_key, get_seeds_and_key},
+};
+
+use {
+    bonfida_utils::{
+        checks::{check_account_key, check_signer},
+        BorshSize, InstructionsAccount,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_error::ProgramError,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        system_program,
+    },
+};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    /// The name to tokenize, the subdomain label for a subdomain
+    pub name: String,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The SPL token program
+    pub token_program: &'a T,
+
+    /// The associated token account program
+    pub associated_token_program: &'a T,
+
+    /// The token metadata program
+    pub token_metadata_program: &'a T,
+
+    /// The name service program account
+    pub web3_name_service: &'a T,
+
+    /// The name account, escrowed with the central state while it is tokenized
+    #[cons(writable)]
+    pub name_account: &'a T,
+
+    /// The mint of the NFT of the name, see [`crate::utils::get_nft_mint_key`]
+    #[cons(writable)]
+    pub nft_mint: &'a T,
+
+    /// The metadata account of the NFT
+    #[cons(writable)]
+    pub nft_metadata: &'a T,
+
+    /// The master edition account of the NFT
+    #[cons(writable)]
+    pub nft_master_edition: &'a T,
+
+    /// The associated token account of the central state, holding the NFT of an unwrapped name
+    #[cons(writable)]
+    pub nft_escrow: &'a T,
+
+    /// The associated token account of the owner for the NFT
+    #[cons(writable)]
+    pub nft_token: &'a T,
+
+    #[cons(writable, signer)]
+    /// The owner of the name, receiving the NFT and paying for its accounts
+    pub owner: &'a T,
+
+    /// The central state
+    pub central_state: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(accounts: &'a [AccountInfo<'b>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Accounts {
+            system_program: next_account_info(accounts_iter)?,
+            token_program: next_account_info(accounts_iter)?,
+            associated_token_program: next_account_info(accounts_iter)?,
+            token_metadata_program: next_account_info(accounts_iter)?,
+            web3_name_service: next_account_info(accounts_iter)?,
+            name_account: next_account_info(accounts_iter)?,
+            nft_mint: next_account_info(accounts_iter)?,
+            nft_metadata: next_account_info(accounts_iter)?,
+            nft_master_edition: next_account_info(accounts_iter)?,
+            nft_escrow: next_account_info(accounts_iter)?,
+            nft_token: next_account_info(accounts_iter)?,
+            owner: next_account_info(accounts_iter)?,
+            central_state: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(accounts.system_program, &system_program::ID)?;
+        check_account_key(accounts.token_program, &spl_token::ID)?;
+        check_account_key(
+            accounts.associated_token_program,
+            &spl_associated_token_account::ID,
+        )?;
+        check_account_key(accounts.token_metadata_program, &mpl_token_metadata::ID)?;
+        check_account_key(accounts.web3_name_service, &spl_name_service::ID)?;
+        check_account_key(accounts.central_state, &central_state::KEY)?;
+
+        // Check owners
+        check_account_owner(accounts.name_account, &spl_name_service::ID)?;
+
+        // Check signer
+        check_signer(accounts.owner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub fn process(_program_id: &Pubkey, accounts: &[AccountInfo], params: Params) -> ProgramResult {
+    let accounts = Accounts::parse(accounts)?;
+
+    let header = NameRecordHeader::unpack_from_slice(&accounts.name_account.data.borrow())?;
+    let parent = Some(header.parent_name).filter(|parent| *parent != Pubkey::default());
+    let (name_key, _) = get_seeds_and_key(
+        accounts.web3_name_service.key,
+        get_hashed_name(&params.name),
+        None,
+        parent.as_ref(),
+    );
+    check_account_key(accounts.name_account, &name_key)?;
+    check_account_key(accounts.owner, &header.owner)?;
+
+    msg!("Escrow the name with the central state");
+    cpi::transfer_name_account(
+        accounts.web3_name_service,
+        accounts.name_account,
+        accounts.owner,
+        &central_state::KEY,
+        &[],
+    )?;
+
+    deliver_nft(&accounts, &params.name)
+}
+
+/// Hands the NFT of a name held by the central state to the owner: the NFT is minted the first
+/// time the name is tokenized, and released from the escrow of the central state afterwards
+pub(crate) fn deliver_nft(accounts: &Accounts<AccountInfo>, name: &str) -> ProgramResult {
+    let (mint_key, mint_seeds) = get_nft_mint_key(accounts.name_account.key);
+    check_account_key(accounts.nft_mint, &mint_key)?;
+    check_account_key(accounts.nft_metadata, &Metadata::find_pda(&mint_key).0)?;
+    check_account_key(
+        accounts.nft_master_edition,
+        &MasterEdition::find_pda(&mint_key).0,
+    )?;
+    check_account_key(
+        accounts.nft_escrow,
+        &get_associated_token_address(&central_state::KEY, &mint_key),
+    )?;
+    check_account_key(
+        accounts.nft_token,
+        &get_associated_token_address(accounts.owner.key, &mint_key),
+    )?;
+
+    let central_state_signer_seeds: &[&[u8]] = &[&crate::ID.to_bytes(), &[central_state::NONCE]];
+
+    invoke(
+        &create_associated_token_account_idempotent(
+            accounts.owner.key,
+            accounts.owner.key,
+            &mint_key,
+            accounts.token_program.key,
+        ),
+        &[
+            accounts.owner.clone(),
+            accounts.nft_token.clone(),
+            accounts.nft_mint.clone(),
+            accounts.system_program.clone(),
+            accounts.token_program.clone(),
+            accounts.associated_token_program.clone(),
+        ],
+    )?;
+
+    if accounts.nft_mint.data_len() > 0 {
+        msg!("Release the NFT from escrow");
+        return invoke_signed(
+            &spl_token::instruction::transfer(
+                accounts.token_program.key,
+                accounts.nft_escrow.key,
+                accounts.nft_token.key,
+                &central_state::KEY,
+                &[],
+                1,
+            )?,
+            &[
+                accounts.nft_escrow.clone(),
+                accounts.nft_token.clone(),
+                accounts.central_state.clone(),
+                accounts.token_program.clone(),
+            ],
+            &[central_state_signer_seeds],
+        );
+    }
+
+    if name.len() > mpl_token_metadata::MAX_NAME_LENGTH {
+        msg!(
+            "Names above {} bytes cannot be tokenized",
+            mpl_token_metadata::MAX_NAME_LENGTH
+        );
+        return Err(AuctionError::NameTooLong.into());
+    }
+
+    msg!("Create the NFT mint");
+    cpi::create_pda_account(
+        accounts.system_program,
+        accounts.owner,
+        accounts.nft_mint,
+        Mint::LEN,
+        &spl_token::ID,
+        &mint_seeds,
+    )?;
+    invoke(
+        &spl_token::instruction::initialize_mint2(
+            accounts.token_program.key,
+            &mint_key,
+            &central_state::KEY,
+            None,
+            0,
+        )?,
+        &[accounts.nft_mint.clone(), accounts.token_program.clone()],
+    )?;
+
+    msg!("Create the NFT metadata");
+    CreateMetadataAccountV3CpiBuilder::new(accounts.token_metadata_program)
+        .metadata(accounts.nft_metadata)
+        .mint(accounts.nft_mint)
+        .mint_authority(accounts.central_state)
+        .payer(accounts.owner)
+        .update_authority(accounts.central_state, true)
+        .system_program(accounts.system_program)
+        .data(DataV2 {
+            name: name.to_owned(),
+            symbol: String::new(),
+            uri: String::new(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        })
+        .is_mutable(true)
+        .invoke_signed(&[central_state_signer_seeds])?;
+
+    msg!("Mint the NFT");
+    invoke_signed(
+        &spl_token::instruction::mint_to(
+            accounts.token_program.key,
+            &mint_key,
+            accounts.nft_token.key,
+            &central_state::KEY,
+            &[],
+            1,
+        )?,
+        &[
+            accounts.nft_mint.clone(),
+            accounts.nft_token.clone(),
+            accounts.central_state.clone(),
+            accounts.token_program.clone(),
+        ],
+        &[central_state_signer_seeds],
+    )?;
+
+    // The master edition takes over the mint authority, the NFT can never be minted again
+    msg!("Create the NFT master edition");
+    CreateMasterEditionV3CpiBuilder::new(accounts.token_metadata_program)
+        .edition(accounts.nft_master_edition)
+        .mint(accounts.nft_mint)
+        .update_authority(accounts.central_state)
+        .mint_authority(accounts.central_state)
+        .payer(accounts.owner)
+        .metadata(accounts.nft_metadata)
+        .token_program(accounts.token_program)
+        .system_program(accounts.system_program)
+        .max_supply(0)
+        .invoke_signed(&[central_state_signer_seeds])
+}
@@ -1,6 +1,15 @@
+use std::mem::size_of;
+
 use borsh::{BorshDeserialize, BorshSerialize};
+use bytemuck::Pod;
+use solana_program::{msg, program_error::ProgramError};
 
+use crate::error::AuctionError;
 
+pub mod auction;
+pub mod bid;
+pub mod config;
+pub mod contribution;
 pub mod record_header;
 
 #[derive(BorshDeserialize, BorshSerialize)]
@@ -8,11 +17,143 @@ pub struct ReverseLookup {
     pub name: String,
 }
 
+/// Discriminator stored in the first byte of every account owned by the program,
+/// followed by the version of the layout
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Tag {
+    Uninitialized,
+    RootRecord,
+    Auction,
+    Config,
+    Contribution,
+    Bid,
+}
+
+impl Tag {
+    /// Size of the tag and version prefix
+    pub const LEN: usize = 2;
+
+    /// Reads the tag of raw account data
+    pub fn of(data: &[u8]) -> Tag {
+        match data.first() {
+            Some(1) => Tag::RootRecord,
+            Some(2) => Tag::Auction,
+            Some(3) => Tag::Config,
+            Some(4) => Tag::Contribution,
+            Some(5) => Tag::Bid,
+            _ => Tag::Uninitialized,
+        }
+    }
+
+    /// Writes the tag and version prefix, returning the rest of the buffer
+    pub fn write(self, dst: &mut [u8], version: u8) -> &mut [u8] {
+        dst[0] = self as u8;
+        dst[1] = version;
+        &mut dst[Self::LEN..]
+    }
+
+    /// Checks the tag and version prefix, returning the rest of the buffer
+    pub fn check(self, src: &[u8], version: u8) -> Result<&[u8], ProgramError> {
+        if src.len() < Self::LEN || Tag::of(src) != self || src[1] != version {
+            msg!("Expected a {:?} account with version {}", self, version);
+            return Err(AuctionError::DataTypeMismatch.into());
+        }
+        Ok(&src[Self::LEN..])
+    }
+
+    /// Checks the prefix and casts the fixed-layout header `T`, returning it with the trailing bytes
+    pub fn load<T: Pod>(self, buffer: &[u8], version: u8) -> Result<(&T, &[u8]), ProgramError> {
+        self.check(buffer, version)?;
+        if buffer.len() < size_of::<T>() {
+            return Err(AuctionError::OutOfBound.into());
+        }
+        let (header, rest) = buffer.split_at(size_of::<T>());
+        let header = bytemuck::try_from_bytes(header).map_err(|_| ProgramError::InvalidAccountData)?;
+        Ok((header, rest))
+    }
+
+    /// Mutable version of [`Tag::load`]
+    pub fn load_mut<T: Pod>(
+        self,
+        buffer: &mut [u8],
+        version: u8,
+    ) -> Result<(&mut T, &mut [u8]), ProgramError> {
+        self.check(buffer, version)?;
+        if buffer.len() < size_of::<T>() {
+            return Err(AuctionError::OutOfBound.into());
+        }
+        let (header, rest) = buffer.split_at_mut(size_of::<T>());
+        let header =
+            bytemuck::try_from_bytes_mut(header).map_err(|_| ProgramError::InvalidAccountData)?;
+        Ok((header, rest))
+    }
+
+    /// Writes the prefix and the name trailing the fixed-layout header `T`, returning the header
+    pub fn init<'a, T: Pod>(
+        self,
+        buffer: &'a mut [u8],
+        version: u8,
+        name: &str,
+    ) -> Result<&'a mut T, ProgramError> {
+        if buffer.len() < size_of::<T>() + name.len() {
+            msg!("The account is too small");
+            return Err(AuctionError::OutOfBound.into());
+        }
+        self.write(buffer, version);
+        let (header, rest) = buffer.split_at_mut(size_of::<T>());
+        rest[..name.len()].copy_from_slice(name.as_bytes());
+        bytemuck::try_from_bytes_mut(header).map_err(|_| ProgramError::InvalidAccountData)
+    }
+}
+
+/// Reads the name trailing a fixed-layout header
+pub fn read_name(rest: &[u8], len: u32) -> Result<&str, ProgramError> {
+    rest.get(..len as usize)
+        .and_then(|name| std::str::from_utf8(name).ok())
+        .ok_or(ProgramError::InvalidAccountData)
+}
+
 
 pub mod constants {
+    use bonfida_utils::tokens::SupportedToken;
+    use solana_program::{pubkey, pubkey::Pubkey};
+
+    /// The authority allowed to initialize the program config
+    pub const AUTHORITY: Pubkey = pubkey!("FNWdNB9uFtZBrw7mNUqdPpMjKjvg4Ft6ZVu2Z511pt8L");
+
+    /// Maximum length of a root name in bytes, which bounds the size of its record
+    pub const MAX_ROOT_NAME_LEN: usize = 64;
+
+    /// Number of seconds after the end of an auction with a hidden reserve
+    /// during which only the seller can settle it
+    pub const RESERVE_REVEAL_PERIOD: i64 = 86_400;
+
+    /// The referrers allowed to receive referral fees, unless `no-referrer-check` is enabled
+    pub const REFERRER_WHITELIST: [Pubkey; 1] =
+        [pubkey!("3ogYncmMM5CmytsGCqKHydmXmKUZ6sGWvizkzqwT7zb1")];
+
+    /// The verified collection whose holders get a discount on the protocol fees
+    pub const DISCOUNT_COLLECTION: Pubkey = pubkey!("Dw74YSxTKVXsztPm3TmwbnfLK8KVaCZw69jVu4LE6uJe");
+
+    /// Discount on the protocol fees for holders of the discount collection, in basis points,
+    /// unless `no-special-discount-fee` is enabled
+    pub const SPECIAL_DISCOUNT_BPS: u64 = 2_000;
+
+    /// SPL mints accepted for donations and bids, valued through their Pyth feed
+    pub const ACCEPTED_TOKENS: [SupportedToken; 3] =
+        [SupportedToken::USDC, SupportedToken::USDT, SupportedToken::Fida];
+
+    /// Maximum age in seconds of a Pyth price used to value tokens
+    pub const MAX_PRICE_AGE: u64 = 60;
 
+    /// Maximum confidence interval of a Pyth price, in basis points of the price
+    pub const MAX_PRICE_CONF_BPS: u64 = 200;
 
+    /// Price multipliers of names of 1 to 4 characters, counted as graphemes
+    pub const LENGTH_PREMIUMS: [u64; 4] = [50, 20, 10, 5];
 
-    pub const CREATE_FEE: u64 = 10000;
+    /// Price multiplier of names made only of emojis
+    pub const EMOJI_PREMIUM: u64 = 2;
 
 }
@@ -0,0 +1,402 @@
+use std::mem::size_of;
+
+use bonfida_utils::BorshSize;
+use borsh::{BorshDeserialize, BorshSerialize};
+use bytemuck::{Pod, Zeroable};
+use solana_program::{msg, program_error::ProgramError, pubkey::Pubkey};
+
+use super::{read_name, Tag};
+use crate::{
+    central_state,
+    error::AuctionError,
+    utils::{get_hashed_name, get_seeds_and_key},
+};
+
+/// How the price of an auction is discovered
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSize)]
+#[repr(u8)]
+pub enum AuctionKind {
+    /// Ascending bids, the highest bid wins when the auction ends
+    English,
+    /// Descending price, the first buyer wins immediately
+    Dutch,
+    /// Bids are committed as hashes, then revealed once bidding has ended
+    Sealed,
+}
+
+/// How the price of a Dutch auction decays to its floor
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize, BorshSize)]
+#[repr(u8)]
+pub enum PriceDecay {
+    /// The price reaches the floor at the end of the auction
+    Linear,
+    /// The premium above the floor halves every decay period
+    Exponential,
+}
+
+/// An auction for a name, followed in the account by the name bytes
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[repr(C)]
+pub struct Auction {
+    pub tag: u8,
+    pub version: u8,
+    /// The [`AuctionKind`] of the auction
+    pub kind: u8,
+    /// The [`PriceDecay`] of a Dutch auction
+    pub decay: u8,
+    /// Length of the name trailing the auction
+    pub name_len: u32,
+    /// The account that created the auction and receives the proceeds
+    pub seller: Pubkey,
+    /// The current highest bidder, `Pubkey::default()` if there is no bid yet
+    pub highest_bidder: Pubkey,
+    /// The minimum amount of the first bid, or the floor price of a Dutch auction, in lamports
+    pub reserve_price: u64,
+    /// The minimum amount by which a bid must exceed the current highest bid, in lamports
+    pub min_increment: u64,
+    /// Unix timestamp from which bids are accepted
+    pub start_time: i64,
+    /// Unix timestamp after which bids are rejected
+    pub end_time: i64,
+    /// Unix timestamp until which sealed bids can be revealed
+    pub reveal_end_time: i64,
+    /// The current highest bid, in lamports or in the value of `bid_token_amount`
+    pub highest_bid: u64,
+    /// The price of a Dutch auction at `start_time`, in lamports
+    pub start_price: u64,
+    /// Number of seconds in which the premium of an exponential Dutch auction halves
+    pub decay_period: i64,
+    /// The second highest revealed bid of a sealed auction, in lamports
+    pub second_highest_bid: u64,
+    /// Whether the winner of a sealed auction pays the second highest bid
+    pub second_price: u8,
+    /// Whether the name existed and was escrowed when the auction was created
+    pub name_escrowed: u8,
+    pub _padding: [u8; 2],
+    /// Number of times a late bid extended `end_time`
+    pub extension_count: u32,
+    /// Number of seconds before `end_time` in which a bid extends the auction
+    pub extension_window: i64,
+    /// Maximum number of seconds by which `end_time` can be extended
+    pub max_extension: i64,
+    /// Number of seconds by which `end_time` has been extended
+    pub total_extension: i64,
+    /// Commitment to a hidden reserve price, zeroed if there is none
+    pub reserve_commitment: [u8; 32],
+    /// The instant purchase price of an English auction, 0 if there is none
+    pub buy_now_price: u64,
+    /// The root name account of a subdomain, `Pubkey::default()` for a top-level name
+    pub parent: Pubkey,
+    /// The mint of the highest bid, `Pubkey::default()` for a bid in lamports
+    pub bid_mint: Pubkey,
+    /// The amount of tokens of the highest bid held by the escrow
+    pub bid_token_amount: u64,
+    /// The referrer of the highest bid, `Pubkey::default()` if there is none
+    pub referrer: Pubkey,
+}
+
+impl Auction {
+    pub const VERSION: u8 = 11;
+
+    /// Size of the auction without the name bytes
+    pub const LEN: usize = size_of::<Self>();
+
+    /// Space required to store an auction for `name`
+    pub fn space(name: &str) -> usize {
+        Self::LEN + name.len()
+    }
+
+    /// Initializes the account data of an auction for `name`
+    pub fn initialize<'a>(buffer: &'a mut [u8], name: &str) -> Result<&'a mut Self, ProgramError> {
+        let auction = Tag::Auction.init::<Self>(buffer, Self::VERSION, name)?;
+        auction.name_len = name.len() as u32;
+        Ok(auction)
+    }
+
+    /// Reads an auction and its name in place
+    pub fn from_buffer(buffer: &[u8]) -> Result<(&Self, &str), ProgramError> {
+        let (auction, rest) = Tag::Auction.load::<Self>(buffer, Self::VERSION)?;
+        Ok((auction, read_name(rest, auction.name_len)?))
+    }
+
+    /// Mutable version of [`Auction::from_buffer`]
+    pub fn from_buffer_mut(buffer: &mut [u8]) -> Result<(&mut Self, &str), ProgramError> {
+        let (auction, rest) = Tag::Auction.load_mut::<Self>(buffer, Self::VERSION)?;
+        let name = read_name(rest, auction.name_len)?;
+        Ok((auction, name))
+    }
+
+    /// Derives the auction account of `name`, under `parent` for a subdomain
+    pub fn find_key(name: &str, parent: Option<&Pubkey>) -> (Pubkey, Vec<u8>) {
+        get_seeds_and_key(
+            &crate::ID,
+            get_hashed_name(name),
+            Some(&central_state::KEY),
+            parent,
+        )
+    }
+
+    /// Derives the system-owned account holding the bids of `auction_key`
+    pub fn find_escrow_key(auction_key: &Pubkey) -> (Pubkey, Vec<u8>) {
+        get_seeds_and_key(
+            &crate::ID,
+            auction_key.to_bytes().to_vec(),
+            Some(&central_state::KEY),
+            Some(auction_key),
+        )
+    }
+
+    /// The lowest amount accepted for the next bid
+    pub fn min_bid(&self) -> Result<u64, ProgramError> {
+        if !self.has_bid() {
+            return Ok(self.reserve_price.max(1));
+        }
+        self.highest_bid
+            .checked_add(self.min_increment.max(1))
+            .ok_or_else(|| AuctionError::NumericalOverflow.into())
+    }
+
+    pub fn has_bid(&self) -> bool {
+        self.highest_bidder != Pubkey::default()
+    }
+
+    pub fn kind(&self) -> Result<AuctionKind, ProgramError> {
+        match self.kind {
+            0 => Ok(AuctionKind::English),
+            1 => Ok(AuctionKind::Dutch),
+            2 => Ok(AuctionKind::Sealed),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    pub fn decay(&self) -> Result<PriceDecay, ProgramError> {
+        match self.decay {
+            0 => Ok(PriceDecay::Linear),
+            1 => Ok(PriceDecay::Exponential),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    /// The root name account of a subdomain auction
+    pub fn parent(&self) -> Option<&Pubkey> {
+        if self.parent == Pubkey::default() {
+            None
+        } else {
+            Some(&self.parent)
+        }
+    }
+
+    /// The mint of the highest bid, `None` for a bid in lamports
+    pub fn bid_mint(&self) -> Option<&Pubkey> {
+        if self.bid_mint == Pubkey::default() {
+            None
+        } else {
+            Some(&self.bid_mint)
+        }
+    }
+
+    /// The referrer of the highest bid, sharing the fees of the auction
+    pub fn referrer(&self) -> Option<&Pubkey> {
+        if self.referrer == Pubkey::default() {
+            None
+        } else {
+            Some(&self.referrer)
+        }
+    }
+
+    /// Whether the name existed and was escrowed with the central state when the auction was
+    /// created, otherwise the winning bid creates it
+    pub fn is_name_escrowed(&self) -> bool {
+        self.name_escrowed != 0
+    }
+
+    /// Whether bids can be paid in tokens, which requires an escrowed top-level name
+    /// since tokens can neither pay for a new name nor fund the reward pool of a root
+    pub fn accepts_tokens(&self) -> bool {
+        self.is_name_escrowed() && self.parent().is_none()
+    }
+
+    pub fn has_hidden_reserve(&self) -> bool {
+        self.reserve_commitment != [0; 32]
+    }
+
+    /// Unix timestamp from which the auction can be settled
+    pub fn settle_time(&self) -> i64 {
+        if self.kind == AuctionKind::Sealed as u8 {
+            self.reveal_end_time
+        } else {
+            self.end_time
+        }
+    }
+
+    /// The amount paid by the winner of a sealed auction, the second highest bid
+    /// or the reserve under second-price settlement
+    pub fn clearing_price(&self) -> u64 {
+        if self.second_price != 0 {
+            self.second_highest_bid.max(self.reserve_price)
+        } else {
+            self.highest_bid
+        }
+    }
+
+    /// Pushes `end_time` to `extension_window` seconds after a bid placed at `now`,
+    /// within the limit of `max_extension`. Returns whether the auction was extended.
+    pub fn extend(&mut self, now: i64) -> Result<bool, ProgramError> {
+        let target = now
+            .checked_add(self.extension_window)
+            .ok_or(AuctionError::NumericalOverflow)?;
+        let extension = target
+            .saturating_sub(self.end_time)
+            .min(self.max_extension.saturating_sub(self.total_extension));
+        if extension <= 0 {
+            return Ok(false);
+        }
+        self.end_time += extension;
+        self.total_extension += extension;
+        self.extension_count += 1;
+        Ok(true)
+    }
+
+    /// Fails unless the auction is of the given kind
+    pub fn check_kind(&self, kind: AuctionKind) -> Result<(), ProgramError> {
+        if self.kind()? != kind {
+            msg!("Expected a {:?} auction", kind);
+            return Err(AuctionError::WrongAuctionKind.into());
+        }
+        Ok(())
+    }
+
+    /// The price of a Dutch auction at `now`, decaying from `start_price` to `reserve_price`
+    pub fn current_price(&self, now: i64) -> Result<u64, ProgramError> {
+        let premium = self.start_price.saturating_sub(self.reserve_price) as u128;
+        let elapsed = now.saturating_sub(self.start_time).max(0) as u128;
+        let premium = match self.decay()? {
+            PriceDecay::Linear => {
+                let duration = self.end_time.saturating_sub(self.start_time).max(1) as u128;
+                premium - premium * elapsed.min(duration) / duration
+            }
+            PriceDecay::Exponential => {
+                // Halve the premium for each full period, and interpolate within the current one
+                let period = self.decay_period.max(1) as u128;
+                let halvings = elapsed / period;
+                if halvings >= 64 {
+                    0
+                } else {
+                    let high = premium >> halvings;
+                    let low = high >> 1;
+                    high - (high - low) * (elapsed % period) / period
+                }
+            }
+        };
+        self.reserve_price
+            .checked_add(premium as u64)
+            .ok_or_else(|| AuctionError::NumericalOverflow.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dutch_auction(decay: PriceDecay) -> Auction {
+        let mut auction = Auction::zeroed();
+        auction.kind = AuctionKind::Dutch as u8;
+        auction.decay = decay as u8;
+        auction.start_time = 1_000;
+        auction.end_time = 2_000;
+        auction.reserve_price = 100;
+        auction.start_price = 1_100;
+        auction.decay_period = 100;
+        auction
+    }
+
+    #[test]
+    fn linear_decay() {
+        let auction = dutch_auction(PriceDecay::Linear);
+        assert_eq!(auction.current_price(0).unwrap(), 1_100);
+        assert_eq!(auction.current_price(1_000).unwrap(), 1_100);
+        assert_eq!(auction.current_price(1_250).unwrap(), 850);
+        assert_eq!(auction.current_price(1_500).unwrap(), 600);
+        assert_eq!(auction.current_price(2_000).unwrap(), 100);
+        assert_eq!(auction.current_price(i64::MAX).unwrap(), 100);
+    }
+
+    #[test]
+    fn exponential_decay() {
+        let auction = dutch_auction(PriceDecay::Exponential);
+        assert_eq!(auction.current_price(1_000).unwrap(), 1_100);
+        assert_eq!(auction.current_price(1_050).unwrap(), 850);
+        assert_eq!(auction.current_price(1_100).unwrap(), 600);
+        assert_eq!(auction.current_price(1_200).unwrap(), 350);
+        // The floor is reached once the premium is halved down to nothing
+        assert_eq!(auction.current_price(2_000).unwrap(), 100);
+    }
+
+    #[test]
+    fn exponential_decay_past_64_halvings() {
+        let mut auction = dutch_auction(PriceDecay::Exponential);
+        auction.start_price = u64::MAX;
+        auction.decay_period = 1;
+        assert_eq!(auction.current_price(1_063).unwrap(), 101);
+        assert_eq!(auction.current_price(1_064).unwrap(), 100);
+        assert_eq!(auction.current_price(i64::MAX).unwrap(), 100);
+    }
+
+    #[test]
+    fn price_without_premium() {
+        let mut auction = dutch_auction(PriceDecay::Linear);
+        auction.start_price = auction.reserve_price;
+        assert_eq!(auction.current_price(1_500).unwrap(), 100);
+    }
+
+    fn english_auction(extension_window: i64, max_extension: i64) -> Auction {
+        let mut auction = Auction::zeroed();
+        auction.start_time = 1_000;
+        auction.end_time = 2_000;
+        auction.extension_window = extension_window;
+        auction.max_extension = max_extension;
+        auction
+    }
+
+    #[test]
+    fn extend_within_window() {
+        let mut auction = english_auction(100, 1_000);
+        assert!(!auction.extend(1_899).unwrap());
+        assert!(!auction.extend(1_900).unwrap());
+        assert_eq!(auction.end_time, 2_000);
+
+        assert!(auction.extend(1_950).unwrap());
+        assert_eq!(auction.end_time, 2_050);
+        assert_eq!(auction.total_extension, 50);
+        assert_eq!(auction.extension_count, 1);
+    }
+
+    #[test]
+    fn extend_up_to_cap() {
+        let mut auction = english_auction(100, 120);
+        assert!(auction.extend(1_950).unwrap());
+        assert!(auction.extend(2_040).unwrap());
+        assert_eq!(auction.end_time, 2_120);
+        assert_eq!(auction.total_extension, 120);
+        assert_eq!(auction.extension_count, 2);
+
+        assert!(!auction.extend(2_110).unwrap());
+        assert_eq!(auction.end_time, 2_120);
+        assert_eq!(auction.extension_count, 2);
+    }
+
+    #[test]
+    fn extend_disabled() {
+        let mut auction = english_auction(0, 0);
+        assert!(!auction.extend(1_999).unwrap());
+        let mut auction = english_auction(100, 0);
+        assert!(!auction.extend(1_999).unwrap());
+        assert_eq!(auction.end_time, 2_000);
+    }
+
+    #[test]
+    fn extend_overflow() {
+        let mut auction = english_auction(i64::MAX, 1_000);
+        assert!(auction.extend(1).is_err());
+    }
+}
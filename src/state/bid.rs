@@ -0,0 +1,71 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    msg,
+    program_error::ProgramError,
+    program_pack::{Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+use super::Tag;
+use crate::{central_state, utils::get_seeds_and_key};
+
+/// A sealed bid committed to an auction, its deposit is held by [`Bid::find_deposit_key`]
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct Bid {
+    /// The auction account
+    pub auction: Pubkey,
+    /// The start time of the auction, which tells apart successive auctions of the same name
+    pub auction_start: i64,
+    /// The bidder
+    pub bidder: Pubkey,
+    /// Hash of the amount, the salt and the bidder, see [`crate::utils::get_commitment`]
+    pub commitment: [u8; 32],
+    /// Lamports locked in the deposit account of the bid
+    pub deposit: u64,
+    /// The revealed amount of the bid, in lamports
+    pub amount: u64,
+    /// Whether the bidder revealed the bid
+    pub revealed: bool,
+}
+
+impl Bid {
+    pub const VERSION: u8 = 2;
+
+    /// Derives the bid account of `bidder` on the auction starting at `auction_start`, the
+    /// auction account being reused by later auctions of the same name
+    pub fn find_key(auction: &Pubkey, auction_start: i64, bidder: &Pubkey) -> (Pubkey, Vec<u8>) {
+        let mut seeds = auction.to_bytes().to_vec();
+        seeds.extend_from_slice(&auction_start.to_le_bytes());
+        get_seeds_and_key(&crate::ID, seeds, Some(bidder), None)
+    }
+
+    /// Derives the system-owned account holding the deposit of a bid, so that it can be paid out
+    /// with system transfers alongside the other transfers of a settlement
+    pub fn find_deposit_key(bid_key: &Pubkey) -> (Pubkey, Vec<u8>) {
+        get_seeds_and_key(
+            &crate::ID,
+            bid_key.to_bytes().to_vec(),
+            Some(&central_state::KEY),
+            Some(bid_key),
+        )
+    }
+}
+
+impl Sealed for Bid {}
+
+impl Pack for Bid {
+    const LEN: usize = Tag::LEN + 121;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = Tag::Bid.write(dst, Self::VERSION);
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut p = Tag::Bid.check(src, Self::VERSION)?;
+        Bid::deserialize(&mut p).map_err(|_| {
+            msg!("Failed to deserialize bid");
+            ProgramError::InvalidAccountData
+        })
+    }
+}
@@ -0,0 +1,96 @@
+use bonfida_utils::checks::{check_account_key, check_account_owner};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_pack::{Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+use super::Tag;
+use crate::{central_state, error::AuctionError, utils::get_seeds_and_key};
+
+/// The tunable parameters of the program
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct Config {
+    /// The account allowed to update the config and to moderate auctions
+    pub admin: Pubkey,
+    /// Lower bound of the funding goal of a root domain, in lamports
+    pub min_root_goal: u64,
+    /// Upper bound of the funding goal of a root domain, in lamports
+    pub max_root_goal: u64,
+    /// Smallest accepted donation, in lamports
+    pub min_donation: u64,
+    /// Lamports set aside at `CreateRoot` to pay for the creation of the root domain, never
+    /// discounted since they fund rent rather than the vault
+    pub root_creation_fee: u64,
+    /// Share of the auction proceeds kept by the vault, in basis points
+    pub auction_fee_bps: u64,
+    /// Share of an unrevealed sealed bid deposit kept by the vault, in basis points
+    pub unrevealed_forfeit_bps: u64,
+    /// Share of the proceeds of a subdomain auction paid to the donors of its root, in basis points
+    pub root_share_bps: u64,
+    /// Fee paid by donors on top of their donation, in basis points
+    pub donation_fee_bps: u64,
+    /// Share of the protocol fees paid to the referrer of a donation or a bid, in basis points
+    pub referrer_fee_bps: u64,
+    /// Minimum reserve price of a name created by an auction before the premiums of
+    /// [`crate::pricing::get_name_price`], in lamports
+    pub min_name_price: u64,
+}
+
+impl Config {
+    pub const VERSION: u8 = 5;
+
+    /// Derives the config account
+    pub fn find_key() -> (Pubkey, Vec<u8>) {
+        get_seeds_and_key(
+            &crate::ID,
+            central_state::KEY.to_bytes().to_vec(),
+            Some(&central_state::KEY),
+            None,
+        )
+    }
+
+    /// Checks and deserializes the config account
+    pub fn from_account_info(account: &AccountInfo) -> Result<Self, ProgramError> {
+        check_account_key(account, &Self::find_key().0)?;
+        check_account_owner(account, &crate::ID)?;
+        Self::unpack_from_slice(&account.data.borrow())
+    }
+
+    pub fn check(&self) -> ProgramResult {
+        if self.min_root_goal > self.max_root_goal
+            || self.auction_fee_bps > 10_000
+            || self.unrevealed_forfeit_bps > 10_000
+            || self.root_share_bps > 10_000
+            || self.donation_fee_bps > 10_000
+            || self.referrer_fee_bps > 10_000
+        {
+            msg!("Inconsistent config parameters");
+            return Err(AuctionError::InvalidConfig.into());
+        }
+        Ok(())
+    }
+}
+
+impl Sealed for Config {}
+
+impl Pack for Config {
+    const LEN: usize = Tag::LEN + 112;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = Tag::Config.write(dst, Self::VERSION);
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut p = Tag::Config.check(src, Self::VERSION)?;
+        Config::deserialize(&mut p).map_err(|_| {
+            msg!("Failed to deserialize config");
+            ProgramError::InvalidAccountData
+        })
+    }
+}
@@ -0,0 +1,79 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    msg,
+    program_error::ProgramError,
+    program_pack::{Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+use super::Tag;
+use crate::utils::get_seeds_and_key;
+
+/// The donations of a donor to a root domain campaign
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct Contribution {
+    /// The root record account funded by the donor
+    pub root_record: Pubkey,
+    /// The donor
+    pub donor: Pubkey,
+    /// Total amount donated, in lamports or in their value when donating tokens
+    pub amount: u64,
+    /// Slot of the first donation
+    pub first_slot: u64,
+    /// Slot of the last donation
+    pub last_slot: u64,
+    /// Number of donations
+    pub count: u64,
+    /// Lamports claimed from the reward pool of the root
+    pub claimed: u64,
+    /// The mint of the donated tokens, the default key for lamports
+    pub mint: Pubkey,
+    /// Total amount of tokens donated
+    pub token_amount: u64,
+}
+
+impl Contribution {
+    pub const VERSION: u8 = 3;
+
+    /// Derives the contribution account of `donor` to `root_record`, one per donated mint
+    pub fn find_key(
+        root_record: &Pubkey,
+        donor: &Pubkey,
+        mint: Option<&Pubkey>,
+    ) -> (Pubkey, Vec<u8>) {
+        get_seeds_and_key(
+            &crate::ID,
+            root_record.to_bytes().to_vec(),
+            Some(donor),
+            mint,
+        )
+    }
+
+    /// The mint of the donated tokens, `None` for lamports
+    pub fn mint(&self) -> Option<&Pubkey> {
+        if self.mint == Pubkey::default() {
+            None
+        } else {
+            Some(&self.mint)
+        }
+    }
+}
+
+impl Sealed for Contribution {}
+
+impl Pack for Contribution {
+    const LEN: usize = Tag::LEN + 144;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = Tag::Contribution.write(dst, Self::VERSION);
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut p = Tag::Contribution.check(src, Self::VERSION)?;
+        Contribution::deserialize(&mut p).map_err(|_| {
+            msg!("Failed to deserialize contribution");
+            ProgramError::InvalidAccountData
+        })
+    }
+}
@@ -1,42 +1,70 @@
-use solana_program::program_pack::Pack;
-use borsh::{BorshDeserialize, BorshSerialize};
-use solana_program::{
-    account_info::AccountInfo,
-    msg,
-    program_error::ProgramError,
-    program_pack::{IsInitialized, Sealed},
-    pubkey::Pubkey,
-};
-// use spl_name_service::state::NameRecordHeader;
-
-#[derive(Clone,Debug, BorshSerialize, BorshDeserialize, PartialEq)]
+use std::mem::size_of;
+
+use bytemuck::{Pod, Zeroable};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use super::{read_name, Tag};
+use crate::{central_state, utils::get_seeds_and_key};
+
+/// The crowdfunding campaign of a root domain, followed in the account by the root name bytes
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[repr(C)]
 pub struct RecordHeader {
+    pub tag: u8,
+    pub version: u8,
+    pub _padding: [u8; 2],
+    /// Length of the name trailing the record
+    pub name_len: u32,
+    /// The name service account of the root domain, which keys its reward pool
     pub root_name_key: Pubkey,
     pub amount: u64,
-    pub name: String,
+    /// Unix timestamp after which donations are rejected and can be refunded
+    pub deadline: i64,
+    /// Amount of lamports to raise before the root domain is created
+    pub goal: u64,
+    /// Lamports of the reward pool already claimed by the donors
+    pub claimed_rewards: u64,
 }
 
-impl Sealed for RecordHeader {}
+impl RecordHeader {
+    pub const VERSION: u8 = 3;
 
-impl Pack for RecordHeader {
-    const LEN: usize = 72;
+    /// Size of the record without the name bytes
+    pub const LEN: usize = size_of::<Self>();
 
-    fn pack_into_slice(&self, dst: &mut [u8]) {
-        let mut slice = dst;
-        self.serialize(&mut slice).unwrap()
+    /// Space required to store the record of `name`
+    pub fn space(name: &str) -> usize {
+        Self::LEN + name.len()
     }
 
-    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        let mut p = src;
-        RecordHeader::deserialize(&mut p).map_err(|_| {
-            msg!("Failed to deserialize name record");
-            ProgramError::InvalidAccountData
-        })
+    /// Initializes the account data of the record of `name`
+    pub fn initialize<'a>(buffer: &'a mut [u8], name: &str) -> Result<&'a mut Self, ProgramError> {
+        let record = Tag::RootRecord.init::<Self>(buffer, Self::VERSION, name)?;
+        record.name_len = name.len() as u32;
+        Ok(record)
     }
-}
 
+    /// Reads a record and its root name in place
+    pub fn from_buffer(buffer: &[u8]) -> Result<(&Self, &str), ProgramError> {
+        let (record, rest) = Tag::RootRecord.load::<Self>(buffer, Self::VERSION)?;
+        Ok((record, read_name(rest, record.name_len)?))
+    }
+
+    /// Derives the system-owned account collecting the share of subdomain auctions
+    /// under `root_name_key`
+    pub fn find_reward_pool_key(root_name_key: &Pubkey) -> (Pubkey, Vec<u8>) {
+        get_seeds_and_key(
+            &crate::ID,
+            root_name_key.to_bytes().to_vec(),
+            None,
+            Some(&central_state::KEY),
+        )
+    }
 
-pub fn write_data(account: &AccountInfo, input: &[u8], offset: usize) {
-    let mut account_data = account.data.borrow_mut();
-    account_data[offset..offset.saturating_add(input.len())].copy_from_slice(input);
+    /// Mutable version of [`RecordHeader::from_buffer`]
+    pub fn from_buffer_mut(buffer: &mut [u8]) -> Result<(&mut Self, &str), ProgramError> {
+        let (record, rest) = Tag::RootRecord.load_mut::<Self>(buffer, Self::VERSION)?;
+        let name = read_name(rest, record.name_len)?;
+        Ok((record, name))
+    }
 }
@@ -1,7 +1,20 @@
-use solana_program::{account_info::AccountInfo, hash::hashv, program_pack::Pack};
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner},
+    pyth::parse_price_v2,
+    tokens::SupportedToken,
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, hash::hashv, msg,
+    program_error::ProgramError, program_pack::Pack, sysvar::Sysvar,
+};
+use spl_associated_token_account::get_associated_token_address;
 use spl_name_service::state::NameRecordHeader;
 
-use crate::error::AuctionError;
+use crate::{
+    central_state,
+    error::AuctionError,
+    state::constants::{ACCEPTED_TOKENS, MAX_PRICE_AGE, MAX_PRICE_CONF_BPS, REFERRER_WHITELIST},
+};
 
 use {
     solana_program::pubkey, solana_program::pubkey::Pubkey,
@@ -13,6 +26,10 @@ pub fn get_hashed_name(name: &str) -> Vec<u8> {
         .to_vec()
 }
 
+/// Hash hiding `amount` until `owner` reveals it with `salt`, computed off-chain
+pub fn get_commitment(amount: u64, salt: &[u8], owner: &Pubkey) -> [u8; 32] {
+    hashv(&[&amount.to_le_bytes(), salt, &owner.to_bytes()]).to_bytes()
+}
 
 ////////////////////////////////////////////////////////////
 
@@ -20,6 +37,204 @@ pub const HASH_PREFIX: &str = "WEB3 Name Service";
 
 ////////////////////////////////////////////////////////////
 
+/// The program vault, a system-owned PDA collecting the protocol revenue
+pub fn get_vault_key() -> (Pubkey, Vec<u8>) {
+    get_seeds_and_key(
+        &crate::ID,
+        central_state::KEY.to_bytes().to_vec(),
+        None,
+        Some(&central_state::KEY),
+    )
+}
+
+/// The mint of the NFT of a tokenized name, whose mint authority is the central state
+pub fn get_nft_mint_key(name_key: &Pubkey) -> (Pubkey, Vec<u8>) {
+    get_seeds_and_key(
+        &crate::ID,
+        name_key.to_bytes().to_vec(),
+        Some(&central_state::KEY),
+        Some(name_key),
+    )
+}
+
+/// Moves lamports out of a program-owned account
+pub fn debit_account(
+    account: &AccountInfo,
+    destination: &AccountInfo,
+    lamports: u64,
+) -> ProgramResult {
+    **account.lamports.borrow_mut() = account
+        .lamports()
+        .checked_sub(lamports)
+        .ok_or(AuctionError::NumericalOverflow)?;
+    **destination.lamports.borrow_mut() = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(AuctionError::NumericalOverflow)?;
+    Ok(())
+}
+
+/// Closes a program-owned account, sending its lamports to `destination`
+pub fn close_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    let lamports = account.lamports();
+    **destination.lamports.borrow_mut() = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(AuctionError::NumericalOverflow)?;
+    **account.lamports.borrow_mut() = 0;
+    account.data.borrow_mut().fill(0);
+    Ok(())
+}
+
+/// Takes the next optional account only if it satisfies `predicate`, so that the optional
+/// accounts following it can be passed without it
+pub fn next_account_if<'a, 'b>(
+    iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+    predicate: impl Fn(&AccountInfo) -> bool,
+) -> Option<&'a AccountInfo<'b>> {
+    match iter.as_slice().first() {
+        Some(account) if predicate(account) => iter.next(),
+        _ => None,
+    }
+}
+
+/// Checks that `referrer` is whitelisted, unless the `no-referrer-check` feature is enabled
+pub fn check_referrer_key(referrer: &Pubkey) -> ProgramResult {
+    if cfg!(not(feature = "no-referrer-check")) && !REFERRER_WHITELIST.contains(referrer) {
+        msg!("The referrer {} is not whitelisted", referrer);
+        return Err(AuctionError::InvalidReferrer.into());
+    }
+    Ok(())
+}
+
+/// Checks that `account` can receive referral fees in `mint`, or in lamports when `None`:
+/// either a referrer or one of its token accounts
+pub fn check_referrer(account: &AccountInfo, mint: Option<&Pubkey>) -> ProgramResult {
+    match mint {
+        Some(mint) => {
+            check_account_owner(account, &spl_token::ID)?;
+            let token_account = spl_token::state::Account::unpack(&account.data.borrow())?;
+            if token_account.mint != *mint {
+                msg!("The referrer token account does not hold {}", mint);
+                return Err(AuctionError::InvalidReferrer.into());
+            }
+            check_referrer_key(&token_account.owner)
+        }
+        None => check_referrer_key(account.key),
+    }
+}
+
+/// Unwraps an optional account, checking that it is the associated token account of `owner`
+/// for `mint`
+pub fn check_associated_token_account<'a, 'b>(
+    account: Option<&'a AccountInfo<'b>>,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> Result<&'a AccountInfo<'b>, ProgramError> {
+    let account = account.ok_or(ProgramError::NotEnoughAccountKeys)?;
+    check_account_key(account, &get_associated_token_address(owner, mint))?;
+    Ok(account)
+}
+
+/// Checks that `mint` is accepted for donations and bids
+pub fn get_accepted_token(mint: &Pubkey) -> Result<SupportedToken, ProgramError> {
+    ACCEPTED_TOKENS
+        .iter()
+        .find(|token| token.mint() == *mint)
+        .copied()
+        .ok_or_else(|| {
+            msg!("The mint {} is not accepted", mint);
+            AuctionError::UnsupportedMint.into()
+        })
+}
+
+/// Reads the USD price of `token` from its Pyth feed as `(price, exponent)`
+fn get_usd_price(
+    token: SupportedToken,
+    feed: &AccountInfo,
+    clock: &Clock,
+) -> Result<(u64, i32), ProgramError> {
+    check_account_key(feed, &token.price_feed_account_key())?;
+    if feed.data_len() < 8 {
+        return Err(AuctionError::InvalidPrice.into());
+    }
+    let update = parse_price_v2(&feed.data.borrow())?;
+    let price = update
+        .get_price_no_older_than(clock, MAX_PRICE_AGE, &token.price_feed())
+        .map_err(|_| {
+            msg!("The price of {:?} is stale or unverified", token);
+            AuctionError::StalePrice
+        })?;
+
+    if price.price <= 0 {
+        msg!("The price of {:?} is not positive", token);
+        return Err(AuctionError::InvalidPrice.into());
+    }
+    let value = price.price as u64;
+    if price.conf as u128 * 10_000 > value as u128 * MAX_PRICE_CONF_BPS as u128 {
+        msg!("The price of {:?} is {} +/- {}", token, value, price.conf);
+        return Err(AuctionError::UncertainPrice.into());
+    }
+
+    Ok((value, price.exponent))
+}
+
+/// Values `amount` tokens of `mint` in lamports through the Pyth feeds of the token and of SOL
+pub fn get_lamport_value(
+    mint: &Pubkey,
+    amount: u64,
+    token_feed: &AccountInfo,
+    sol_feed: &AccountInfo,
+) -> Result<u64, ProgramError> {
+    let token = get_accepted_token(mint)?;
+    let clock = Clock::get()?;
+    let (token_price, token_exponent) = get_usd_price(token, token_feed, &clock)?;
+    let (sol_price, sol_exponent) = get_usd_price(SupportedToken::Sol, sol_feed, &clock)?;
+
+    let lamports = convert_to_lamports(
+        amount,
+        token.decimals(),
+        (token_price, token_exponent),
+        (sol_price, sol_exponent),
+    )?;
+    msg!("{} tokens of {:?} are worth {} lamports", amount, token, lamports);
+
+    Ok(lamports)
+}
+
+/// Converts `amount` base units of a token with `decimals` decimals into lamports, given the
+/// USD prices of the token and of SOL as Pyth mantissa and exponent pairs
+fn convert_to_lamports(
+    amount: u64,
+    decimals: u8,
+    (token_price, token_exponent): (u64, i32),
+    (sol_price, sol_exponent): (u64, i32),
+) -> Result<u64, ProgramError> {
+    // amount * token_price * 10^token_exponent / 10^token_decimals
+    //     / (sol_price * 10^sol_exponent) * 10^sol_decimals
+    let scale =
+        token_exponent - sol_exponent + SupportedToken::Sol.decimals() as i32 - decimals as i32;
+    let mut numerator = amount as u128 * token_price as u128;
+    let mut denominator = sol_price as u128;
+    if scale >= 0 {
+        numerator = 10u128
+            .checked_pow(scale as u32)
+            .and_then(|factor| numerator.checked_mul(factor))
+            .ok_or(AuctionError::NumericalOverflow)?;
+    } else {
+        denominator = 10u128
+            .checked_pow(-scale as u32)
+            .and_then(|factor| denominator.checked_mul(factor))
+            .ok_or(AuctionError::NumericalOverflow)?;
+    }
+
+    let lamports = numerator / denominator;
+    if lamports > u64::MAX as u128 {
+        return Err(AuctionError::NumericalOverflow.into());
+    }
+    Ok(lamports as u64)
+}
+
 pub fn get_seeds_and_key(
     program_id: &Pubkey,
     hashed_name: Vec<u8>, // Hashing is done off-chain
@@ -49,3 +264,45 @@ pub fn get_seeds_and_key(
 
     (name_account_key, seeds_vec)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+    #[test]
+    fn convert_with_positive_scale() {
+        // 150 USDC at $1 with SOL at $150
+        let lamports =
+            convert_to_lamports(150_000_000, 6, (100_000_000, -8), (15_000_000_000, -8)).unwrap();
+        assert_eq!(lamports, LAMPORTS_PER_SOL);
+    }
+
+    #[test]
+    fn convert_with_negative_scale() {
+        // 3 tokens of 9 decimals at $50 with SOL at $100
+        let lamports = convert_to_lamports(
+            3_000_000_000,
+            9,
+            (500_000_000_000, -10),
+            (10_000_000, -5),
+        )
+        .unwrap();
+        assert_eq!(lamports, 3 * LAMPORTS_PER_SOL / 2);
+    }
+
+    #[test]
+    fn convert_rounds_down() {
+        // 1 base unit of USDC with SOL at $150 is worth 6.67 lamports
+        let lamports = convert_to_lamports(1, 6, (100_000_000, -8), (15_000_000_000, -8)).unwrap();
+        assert_eq!(lamports, 6);
+    }
+
+    #[test]
+    fn convert_overflow() {
+        assert!(convert_to_lamports(u64::MAX, 0, (u64::MAX, 0), (1, 0)).is_err());
+        assert!(convert_to_lamports(1, 0, (1, 30), (1, -30)).is_err());
+        assert!(convert_to_lamports(1, 0, (1, -30), (u64::MAX, 30)).is_err());
+    }
+}
@@ -0,0 +1,673 @@
+use auction::{
+    central_state,
+    instruction::{
+        commit_bid, create_auction, place_bid, reclaim_bid, refund_donation, reveal_bid,
+        settle_auction,
+    },
+    state::{
+        auction::{Auction, AuctionKind, PriceDecay},
+        bid::Bid,
+        config::Config,
+        contribution::Contribution,
+        record_header::RecordHeader,
+    },
+    utils::{get_commitment, get_hashed_name, get_seeds_and_key, get_vault_key},
+};
+use solana_program::{
+    clock::Clock, instruction::Instruction, native_token::LAMPORTS_PER_SOL, program_pack::Pack,
+    pubkey::Pubkey, system_instruction, system_program,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use spl_name_service::state::NameRecordHeader;
+
+const MIN_NAME_PRICE: u64 = LAMPORTS_PER_SOL / 10;
+
+async fn setup() -> ProgramTestContext {
+    let mut program_test = ProgramTest::new(
+        "auction",
+        auction::ID,
+        processor!(auction::entrypoint::process_instruction),
+    );
+    program_test.add_program(
+        "spl_name_service",
+        spl_name_service::ID,
+        processor!(spl_name_service::processor::Processor::process_instruction),
+    );
+
+    program_test.add_account(
+        central_state::KEY,
+        Account {
+            lamports: 1_000_000,
+            data: vec![central_state::NONCE],
+            owner: auction::ID,
+            ..Account::default()
+        },
+    );
+
+    // The config is written directly instead of going through InitConfig
+    let config = Config {
+        admin: Pubkey::new_unique(),
+        min_root_goal: LAMPORTS_PER_SOL,
+        max_root_goal: 100 * LAMPORTS_PER_SOL,
+        min_donation: 1_000_000,
+        root_creation_fee: LAMPORTS_PER_SOL,
+        auction_fee_bps: 500,
+        unrevealed_forfeit_bps: 1_000,
+        root_share_bps: 2_000,
+        donation_fee_bps: 0,
+        referrer_fee_bps: 0,
+        min_name_price: MIN_NAME_PRICE,
+    };
+    let mut data = vec![0; Config::LEN];
+    config.pack_into_slice(&mut data);
+    program_test.add_account(
+        Config::find_key().0,
+        Account {
+            lamports: 10_000_000,
+            data,
+            owner: auction::ID,
+            ..Account::default()
+        },
+    );
+    program_test.add_account(
+        get_vault_key().0,
+        Account {
+            lamports: 1_000_000,
+            ..Account::default()
+        },
+    );
+
+    program_test.start_with_context().await
+}
+
+async fn sign_send_instructions(
+    ctx: &mut ProgramTestContext,
+    instructions: Vec<Instruction>,
+    signers: Vec<&Keypair>,
+) -> Result<(), BanksClientError> {
+    let slot = ctx.banks_client.get_root_slot().await?;
+    ctx.warp_to_slot(slot + 1).unwrap();
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(&ctx.payer.pubkey()));
+    let mut payer_signers = vec![&ctx.payer];
+    payer_signers.extend(signers);
+    transaction.partial_sign(&payer_signers, ctx.last_blockhash);
+    ctx.banks_client.process_transaction(transaction).await
+}
+
+async fn advance_clock_by(ctx: &mut ProgramTestContext, sec: i64) {
+    let mut clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp += sec;
+    ctx.set_sysvar(&clock);
+    let slot = ctx.banks_client.get_root_slot().await.unwrap();
+    ctx.warp_to_slot(slot + 1).unwrap();
+}
+
+async fn funded_keypair(ctx: &mut ProgramTestContext, lamports: u64) -> Keypair {
+    let keypair = Keypair::new();
+    let ix = system_instruction::transfer(&ctx.payer.pubkey(), &keypair.pubkey(), lamports);
+    sign_send_instructions(ctx, vec![ix], vec![]).await.unwrap();
+    keypair
+}
+
+async fn balance(ctx: &mut ProgramTestContext, key: &Pubkey) -> u64 {
+    ctx.banks_client.get_balance(*key).await.unwrap()
+}
+
+async fn exists(ctx: &mut ProgramTestContext, key: &Pubkey) -> bool {
+    ctx.banks_client.get_account(*key).await.unwrap().is_some()
+}
+
+/// The keys of an auction for a new top-level name
+struct AuctionKeys {
+    name: String,
+    name_account: Pubkey,
+    auction: Pubkey,
+    escrow: Pubkey,
+}
+
+impl AuctionKeys {
+    fn new(name: &str) -> Self {
+        let (name_account, _) =
+            get_seeds_and_key(&spl_name_service::ID, get_hashed_name(name), None, None);
+        let (auction, _) = Auction::find_key(name, None);
+        let (escrow, _) = Auction::find_escrow_key(&auction);
+        Self {
+            name: name.to_owned(),
+            name_account,
+            auction,
+            escrow,
+        }
+    }
+}
+
+fn create_auction_ix(
+    keys: &AuctionKeys,
+    seller: &Pubkey,
+    kind: AuctionKind,
+    second_price: bool,
+    reserve_price: u64,
+    duration: i64,
+) -> Instruction {
+    create_auction(
+        create_auction::Accounts {
+            system_program: &system_program::ID,
+            web3_name_service: &spl_name_service::ID,
+            name_account: &keys.name_account,
+            auction: &keys.auction,
+            escrow: &keys.escrow,
+            seller,
+            config: &Config::find_key().0,
+            root_record: None,
+        },
+        create_auction::Params {
+            name: keys.name.clone(),
+            root_name: None,
+            kind,
+            reserve_price,
+            min_increment: LAMPORTS_PER_SOL / 100,
+            start_delay: 0,
+            duration,
+            start_price: 0,
+            decay: PriceDecay::Linear,
+            decay_period: 0,
+            reveal_duration: if kind == AuctionKind::Sealed {
+                duration
+            } else {
+                0
+            },
+            second_price,
+            extension_window: 0,
+            max_extension: 0,
+            reserve_commitment: None,
+            buy_now_price: 0,
+        },
+    )
+}
+
+fn place_bid_ix(
+    keys: &AuctionKeys,
+    bidder: &Pubkey,
+    previous_bidder: Option<&Pubkey>,
+    amount: u64,
+) -> Instruction {
+    place_bid(
+        place_bid::Accounts {
+            system_program: &system_program::ID,
+            auction: &keys.auction,
+            escrow: &keys.escrow,
+            bidder,
+            previous_bidder,
+            token_program: None,
+            escrow_token: None,
+            bidder_token: None,
+            token_feed: None,
+            sol_feed: None,
+            previous_escrow_token: None,
+        },
+        place_bid::Params {
+            amount,
+            mint: None,
+            referrer: None,
+        },
+    )
+}
+
+fn settle_auction_ix(
+    keys: &AuctionKeys,
+    winner: &Pubkey,
+    seller: &Pubkey,
+    winning_bid: Option<&Pubkey>,
+) -> Instruction {
+    let winning_deposit = winning_bid.map(|bid| Bid::find_deposit_key(bid).0);
+    settle_auction(
+        settle_auction::Accounts {
+            system_program: &system_program::ID,
+            web3_name_service: &spl_name_service::ID,
+            name_account: &keys.name_account,
+            parent_name: &Pubkey::default(),
+            reward_pool: &Pubkey::default(),
+            auction: &keys.auction,
+            escrow: &keys.escrow,
+            winner,
+            seller,
+            vault: &get_vault_key().0,
+            central_state: &central_state::KEY,
+            config: &Config::find_key().0,
+            winning_bid,
+            winning_deposit: winning_deposit.as_ref(),
+            token_program: None,
+            escrow_token: None,
+            winner_token: None,
+            seller_token: None,
+            vault_token: None,
+            discount_metadata: None,
+            discount_token: None,
+            token_metadata_program: None,
+            nft_mint: None,
+            nft_metadata: None,
+            nft_master_edition: None,
+            nft_escrow: None,
+            nft_token: None,
+            nft_payer: None,
+            nft_token_program: None,
+            associated_token_program: None,
+            referrer: None,
+        },
+        settle_auction::Params {
+            reserve: None,
+            reserve_salt: vec![],
+        },
+    )
+}
+
+async fn name_owner(ctx: &mut ProgramTestContext, name_account: &Pubkey) -> Pubkey {
+    let account = ctx
+        .banks_client
+        .get_account(*name_account)
+        .await
+        .unwrap()
+        .unwrap();
+    NameRecordHeader::unpack_from_slice(&account.data)
+        .unwrap()
+        .owner
+}
+
+#[tokio::test]
+async fn english_auction_settlement() {
+    let mut ctx = setup().await;
+    let seller = funded_keypair(&mut ctx, 10 * LAMPORTS_PER_SOL).await;
+    let alice = funded_keypair(&mut ctx, 10 * LAMPORTS_PER_SOL).await;
+    let bob = funded_keypair(&mut ctx, 10 * LAMPORTS_PER_SOL).await;
+    let keys = AuctionKeys::new("english-settlement");
+
+    let ix = create_auction_ix(
+        &keys,
+        &seller.pubkey(),
+        AuctionKind::English,
+        false,
+        MIN_NAME_PRICE,
+        3_600,
+    );
+    sign_send_instructions(&mut ctx, vec![ix], vec![&seller])
+        .await
+        .unwrap();
+
+    // A bid below the reserve is rejected
+    let ix = place_bid_ix(&keys, &alice.pubkey(), None, MIN_NAME_PRICE - 1);
+    assert!(sign_send_instructions(&mut ctx, vec![ix], vec![&alice])
+        .await
+        .is_err());
+
+    let ix = place_bid_ix(&keys, &alice.pubkey(), None, LAMPORTS_PER_SOL / 2);
+    sign_send_instructions(&mut ctx, vec![ix], vec![&alice])
+        .await
+        .unwrap();
+    assert_eq!(
+        balance(&mut ctx, &alice.pubkey()).await,
+        10 * LAMPORTS_PER_SOL - LAMPORTS_PER_SOL / 2
+    );
+
+    // The outbid bidder is refunded in full
+    let ix = place_bid_ix(
+        &keys,
+        &bob.pubkey(),
+        Some(&alice.pubkey()),
+        LAMPORTS_PER_SOL,
+    );
+    sign_send_instructions(&mut ctx, vec![ix], vec![&bob])
+        .await
+        .unwrap();
+    assert_eq!(
+        balance(&mut ctx, &alice.pubkey()).await,
+        10 * LAMPORTS_PER_SOL
+    );
+
+    // The auction cannot be settled before it ends
+    let ix = settle_auction_ix(&keys, &bob.pubkey(), &seller.pubkey(), None);
+    assert!(sign_send_instructions(&mut ctx, vec![ix], vec![])
+        .await
+        .is_err());
+
+    advance_clock_by(&mut ctx, 3_601).await;
+    let vault_before = balance(&mut ctx, &get_vault_key().0).await;
+    let ix = settle_auction_ix(&keys, &bob.pubkey(), &seller.pubkey(), None);
+    sign_send_instructions(&mut ctx, vec![ix], vec![])
+        .await
+        .unwrap();
+
+    assert_eq!(name_owner(&mut ctx, &keys.name_account).await, bob.pubkey());
+    assert_eq!(balance(&mut ctx, &bob.pubkey()).await, 9 * LAMPORTS_PER_SOL);
+    // The name was created by the auction, the bid minus the name rent goes to the vault
+    let name_rent = ctx
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap()
+        .minimum_balance(NameRecordHeader::LEN);
+    assert_eq!(
+        balance(&mut ctx, &get_vault_key().0).await,
+        vault_before + LAMPORTS_PER_SOL - name_rent
+    );
+    assert!(!exists(&mut ctx, &keys.auction).await);
+    assert_eq!(balance(&mut ctx, &keys.escrow).await, 0);
+}
+
+#[tokio::test]
+async fn settlement_without_bids() {
+    let mut ctx = setup().await;
+    let seller = funded_keypair(&mut ctx, 10 * LAMPORTS_PER_SOL).await;
+    let keys = AuctionKeys::new("no-bids");
+
+    let ix = create_auction_ix(
+        &keys,
+        &seller.pubkey(),
+        AuctionKind::English,
+        false,
+        MIN_NAME_PRICE,
+        60,
+    );
+    sign_send_instructions(&mut ctx, vec![ix], vec![&seller])
+        .await
+        .unwrap();
+
+    advance_clock_by(&mut ctx, 61).await;
+    let vault_before = balance(&mut ctx, &get_vault_key().0).await;
+    let ix = settle_auction_ix(&keys, &seller.pubkey(), &seller.pubkey(), None);
+    sign_send_instructions(&mut ctx, vec![ix], vec![])
+        .await
+        .unwrap();
+
+    // No name is created and the seller gets the rent of the auction and the escrow back
+    assert!(!exists(&mut ctx, &keys.name_account).await);
+    assert!(!exists(&mut ctx, &keys.auction).await);
+    assert_eq!(
+        balance(&mut ctx, &seller.pubkey()).await,
+        10 * LAMPORTS_PER_SOL
+    );
+    assert_eq!(balance(&mut ctx, &get_vault_key().0).await, vault_before);
+}
+
+/// Settles a sealed auction where bids of 1 and 2 SOL are revealed, the winner paying `price`
+async fn sealed_settlement(name: &str, second_price: bool, price: u64) {
+    let mut ctx = setup().await;
+    let seller = funded_keypair(&mut ctx, 10 * LAMPORTS_PER_SOL).await;
+    let alice = funded_keypair(&mut ctx, 10 * LAMPORTS_PER_SOL).await;
+    let bob = funded_keypair(&mut ctx, 10 * LAMPORTS_PER_SOL).await;
+    let keys = AuctionKeys::new(name);
+
+    let ix = create_auction_ix(
+        &keys,
+        &seller.pubkey(),
+        AuctionKind::Sealed,
+        second_price,
+        MIN_NAME_PRICE,
+        600,
+    );
+    sign_send_instructions(&mut ctx, vec![ix], vec![&seller])
+        .await
+        .unwrap();
+    let start_time = {
+        let account = ctx
+            .banks_client
+            .get_account(keys.auction)
+            .await
+            .unwrap()
+            .unwrap();
+        Auction::from_buffer(&account.data).unwrap().0.start_time
+    };
+
+    let bids = [
+        (&alice, LAMPORTS_PER_SOL, 2 * LAMPORTS_PER_SOL),
+        (&bob, 2 * LAMPORTS_PER_SOL, 3 * LAMPORTS_PER_SOL),
+    ];
+    let salt = b"salt".to_vec();
+    for (bidder, amount, deposit) in bids.iter() {
+        let (bid_key, _) = Bid::find_key(&keys.auction, start_time, &bidder.pubkey());
+        let ix = commit_bid(
+            commit_bid::Accounts {
+                system_program: &system_program::ID,
+                auction: &keys.auction,
+                bid: &bid_key,
+                deposit: &Bid::find_deposit_key(&bid_key).0,
+                bidder: &bidder.pubkey(),
+            },
+            commit_bid::Params {
+                commitment: get_commitment(*amount, &salt, &bidder.pubkey()).to_vec(),
+                deposit: *deposit,
+            },
+        );
+        sign_send_instructions(&mut ctx, vec![ix], vec![*bidder])
+            .await
+            .unwrap();
+    }
+
+    advance_clock_by(&mut ctx, 601).await;
+    for (bidder, amount, _) in bids.iter() {
+        let (bid_key, _) = Bid::find_key(&keys.auction, start_time, &bidder.pubkey());
+        let ix = reveal_bid(
+            reveal_bid::Accounts {
+                auction: &keys.auction,
+                bid: &bid_key,
+                bidder: &bidder.pubkey(),
+            },
+            reveal_bid::Params {
+                amount: *amount,
+                salt: salt.clone(),
+            },
+        );
+        sign_send_instructions(&mut ctx, vec![ix], vec![*bidder])
+            .await
+            .unwrap();
+    }
+
+    advance_clock_by(&mut ctx, 600).await;
+    let vault_before = balance(&mut ctx, &get_vault_key().0).await;
+    let (bob_bid, _) = Bid::find_key(&keys.auction, start_time, &bob.pubkey());
+    let ix = settle_auction_ix(&keys, &bob.pubkey(), &seller.pubkey(), Some(&bob_bid));
+    sign_send_instructions(&mut ctx, vec![ix], vec![])
+        .await
+        .unwrap();
+
+    // The winner pays the price and gets the rest of the deposit and the rent of the bid back
+    assert_eq!(name_owner(&mut ctx, &keys.name_account).await, bob.pubkey());
+    assert!(!exists(&mut ctx, &bob_bid).await);
+    assert!(!exists(&mut ctx, &Bid::find_deposit_key(&bob_bid).0).await);
+    assert_eq!(
+        balance(&mut ctx, &bob.pubkey()).await,
+        10 * LAMPORTS_PER_SOL - price
+    );
+    let name_rent = ctx
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap()
+        .minimum_balance(NameRecordHeader::LEN);
+    assert_eq!(
+        balance(&mut ctx, &get_vault_key().0).await,
+        vault_before + price - name_rent
+    );
+
+    // The losing bid is reclaimed in full once the auction is settled
+    let (alice_bid, _) = Bid::find_key(&keys.auction, start_time, &alice.pubkey());
+    let ix = reclaim_bid(
+        reclaim_bid::Accounts {
+            system_program: &system_program::ID,
+            auction: &keys.auction,
+            bid: &alice_bid,
+            deposit: &Bid::find_deposit_key(&alice_bid).0,
+            bidder: &alice.pubkey(),
+            vault: &get_vault_key().0,
+            config: &Config::find_key().0,
+        },
+        reclaim_bid::Params {},
+    );
+    sign_send_instructions(&mut ctx, vec![ix], vec![])
+        .await
+        .unwrap();
+    assert_eq!(
+        balance(&mut ctx, &alice.pubkey()).await,
+        10 * LAMPORTS_PER_SOL
+    );
+}
+
+#[tokio::test]
+async fn sealed_first_price_settlement() {
+    sealed_settlement("sealed-first-price", false, 2 * LAMPORTS_PER_SOL).await;
+}
+
+#[tokio::test]
+async fn sealed_second_price_settlement() {
+    sealed_settlement("sealed-second-price", true, LAMPORTS_PER_SOL).await;
+}
+
+/// Seeds a root domain campaign ending in `duration` seconds with a single lamport donation
+async fn seed_campaign(
+    ctx: &mut ProgramTestContext,
+    donor: &Pubkey,
+    amount: u64,
+    goal: u64,
+    duration: i64,
+) -> (Pubkey, Pubkey) {
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+
+    let root_name = "campaign";
+    let root_record = Pubkey::new_unique();
+    let mut data = vec![0; RecordHeader::space(root_name)];
+    let header = RecordHeader::initialize(&mut data, root_name).unwrap();
+    header.amount = amount;
+    header.goal = goal;
+    header.deadline = clock.unix_timestamp + duration;
+    let account = Account {
+        lamports: rent.minimum_balance(data.len()),
+        data,
+        owner: auction::ID,
+        ..Account::default()
+    };
+    ctx.set_account(&root_record, &AccountSharedData::from(account));
+
+    let (contribution_key, _) = Contribution::find_key(&root_record, donor, None);
+    let contribution = Contribution {
+        root_record,
+        donor: *donor,
+        amount,
+        first_slot: 0,
+        last_slot: 0,
+        count: 1,
+        claimed: 0,
+        mint: Pubkey::default(),
+        token_amount: 0,
+    };
+    let mut data = vec![0; Contribution::LEN];
+    contribution.pack_into_slice(&mut data);
+    let account = Account {
+        lamports: rent.minimum_balance(data.len()),
+        data,
+        owner: auction::ID,
+        ..Account::default()
+    };
+    ctx.set_account(&contribution_key, &AccountSharedData::from(account));
+
+    // The donation is held by the vault
+    let ix = system_instruction::transfer(&ctx.payer.pubkey(), &get_vault_key().0, amount);
+    sign_send_instructions(ctx, vec![ix], vec![]).await.unwrap();
+
+    (root_record, contribution_key)
+}
+
+fn refund_donation_ix(root_record: &Pubkey, contribution: &Pubkey, donor: &Pubkey) -> Instruction {
+    refund_donation(
+        refund_donation::Accounts {
+            system_program: &system_program::ID,
+            vault: &get_vault_key().0,
+            root_cord_account: root_record,
+            contribution,
+            donor,
+            token_program: None,
+            vault_token: None,
+            donor_token: None,
+        },
+        refund_donation::Params {},
+    )
+}
+
+#[tokio::test]
+async fn refund_of_failed_campaign() {
+    let mut ctx = setup().await;
+    let donor = funded_keypair(&mut ctx, LAMPORTS_PER_SOL).await;
+    let (root_record, contribution) = seed_campaign(
+        &mut ctx,
+        &donor.pubkey(),
+        2 * LAMPORTS_PER_SOL,
+        10 * LAMPORTS_PER_SOL,
+        3_600,
+    )
+    .await;
+    let contribution_rent = balance(&mut ctx, &contribution).await;
+
+    // Donations are locked until the deadline
+    let ix = refund_donation_ix(&root_record, &contribution, &donor.pubkey());
+    assert!(sign_send_instructions(&mut ctx, vec![ix], vec![&donor])
+        .await
+        .is_err());
+
+    advance_clock_by(&mut ctx, 3_601).await;
+    let vault_before = balance(&mut ctx, &get_vault_key().0).await;
+    let ix = refund_donation_ix(&root_record, &contribution, &donor.pubkey());
+    sign_send_instructions(&mut ctx, vec![ix], vec![&donor])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        balance(&mut ctx, &donor.pubkey()).await,
+        3 * LAMPORTS_PER_SOL + contribution_rent
+    );
+    assert_eq!(
+        balance(&mut ctx, &get_vault_key().0).await,
+        vault_before - 2 * LAMPORTS_PER_SOL
+    );
+    assert!(!exists(&mut ctx, &contribution).await);
+    let account = ctx
+        .banks_client
+        .get_account(root_record)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        RecordHeader::from_buffer(&account.data).unwrap().0.amount,
+        0
+    );
+
+    // The contribution is closed, the donation cannot be refunded twice
+    let ix = refund_donation_ix(&root_record, &contribution, &donor.pubkey());
+    assert!(sign_send_instructions(&mut ctx, vec![ix], vec![&donor])
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn refund_of_funded_campaign() {
+    let mut ctx = setup().await;
+    let donor = funded_keypair(&mut ctx, LAMPORTS_PER_SOL).await;
+    let (root_record, contribution) = seed_campaign(
+        &mut ctx,
+        &donor.pubkey(),
+        10 * LAMPORTS_PER_SOL,
+        10 * LAMPORTS_PER_SOL,
+        60,
+    )
+    .await;
+
+    // The goal was reached, the donations paid for the root domain
+    advance_clock_by(&mut ctx, 61).await;
+    let ix = refund_donation_ix(&root_record, &contribution, &donor.pubkey());
+    assert!(sign_send_instructions(&mut ctx, vec![ix], vec![&donor])
+        .await
+        .is_err());
+    assert!(exists(&mut ctx, &contribution).await);
+}